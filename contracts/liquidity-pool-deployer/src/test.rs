@@ -4,16 +4,26 @@ extern crate std;
 
 use crate::{liquidity_pool, LiquidityPoolDeployer, LiquidityPoolDeployerClient};
 use soroban_sdk::{
-    testutils::Address as _,
+    testutils::{Address as _, Ledger},
     token::{self},
-    Address, BytesN, Env,
+    vec, Address, BytesN, Env,
 };
 
+// Build of the v0.1.0 release, which stores the pool in the version 1 layout.
+// It is rebuilt with testdata/build_liquidity_pool_v0_1_0.sh.
+mod liquidity_pool_v0_1_0 {
+    soroban_sdk::contractimport!(file = "testdata/liquidity_pool_v0_1_0.wasm");
+}
+
+const LOAN_DURATION: u64 = 30 * 86400;
+
 fn create_token_contract<'a>(
     env: &Env,
     admin: &Address,
 ) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
-    let contract_address = env.register_stellar_asset_contract(admin.clone());
+    let contract_address = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
     (
         token::Client::new(env, &contract_address),
         token::StellarAssetClient::new(env, &contract_address),
@@ -23,6 +33,7 @@ fn create_token_contract<'a>(
 #[test]
 fn test_deploy_from_contract() {
     let env = Env::default();
+    env.budget().reset_unlimited();
     let deployer_client =
         LiquidityPoolDeployerClient::new(&env, &env.register_contract(None, LiquidityPoolDeployer));
 
@@ -33,9 +44,160 @@ fn test_deploy_from_contract() {
     let (token, _token_client) = create_token_contract(&env, &token_admin);
 
     env.mock_all_auths();
-    let (contract_id, _contract) = deployer_client.deploy(&admin, &salt, &token.address);
+    let (contract_id, _contract) = deployer_client.deploy(&admin, &salt, &token.address, &1u32);
+
+    let client = liquidity_pool::Client::new(&env, &contract_id);
+    assert_eq!(client.total_assets(), 0i128);
+}
+
+#[test]
+fn test_migrate_from_v0_1_0() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let admin = Address::generate(&env);
+    let lender1 = Address::generate(&env);
+    let lender2 = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token, token_client) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register_contract_wasm(None, liquidity_pool_v0_1_0::WASM);
+    let previous_client = liquidity_pool_v0_1_0::Client::new(&env, &contract_id);
+
+    previous_client.initialize(&admin, &token.address);
+    previous_client.add_lender(&lender1);
+    previous_client.add_lender(&lender2);
+    previous_client.add_borrower(&borrower);
+    token_client.mint(&lender1, &1000i128);
+    token_client.mint(&lender2, &3000i128);
+    previous_client.deposit(&lender1, &1000i128);
+    previous_client.deposit(&lender2, &3000i128);
+    let loan_id = previous_client.loan(&borrower, &2000i128);
+
+    assert_eq!(previous_client.balance(&lender1), 500i128);
+    assert_eq!(previous_client.balance(&lender2), 1500i128);
+
+    // v0.1.0 has no upgrade entry point, so the test environment installs the
+    // current build at the same address, keeping the storage.
+    env.register_contract_wasm(&contract_id, liquidity_pool::WASM);
 
     let client = liquidity_pool::Client::new(&env, &contract_id);
-    let total_balance = client.balance(&admin);
-    assert_eq!(total_balance, 0i128);
+    client.migrate(&vec![&env, lender1.clone()], &vec![&env]);
+    client.migrate(&vec![&env, lender2.clone()], &vec![&env, borrower.clone()]);
+
+    assert_eq!(client.schema_version(), 1u32);
+
+    client.finalize_migration();
+
+    assert_eq!(client.schema_version(), 2u32);
+    assert_eq!(client.balance(&lender1), 1000i128);
+    assert_eq!(client.balance(&lender2), 3000i128);
+    assert_eq!(client.total_assets(), 4000i128);
+    assert_eq!(
+        client.convert_to_assets(&1000i128, &liquidity_pool::Tranche::Senior),
+        1000i128
+    );
+    assert_eq!(client.loan_info(&borrower, &loan_id).principal, 2000i128);
+    assert!(client
+        .try_migrate(&vec![&env, lender1.clone()], &vec![&env])
+        .is_err());
+    assert!(client.try_finalize_migration().is_err());
+    assert_eq!(client.treasury(), admin);
+    assert_eq!(client.decimals(), token.decimals());
+
+    token_client.mint(&borrower, &1i128);
+    client.repay_loan(&borrower, &loan_id, &2000i128);
+
+    let loan_id = client.loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 10 * 86400);
+
+    assert_eq!(
+        client.repay_loan(&borrower, &loan_id, &1001i128),
+        liquidity_pool::LoanStatus::Repaid
+    );
+    assert_eq!(token.balance(&borrower), 0i128);
+
+    client.withdraw(&lender1, &1000i128, &liquidity_pool::Tranche::Senior);
+    client.withdraw(&lender2, &1000i128, &liquidity_pool::Tranche::Senior);
+
+    assert_eq!(token.balance(&lender1), 1000i128);
+    assert_eq!(token.balance(&lender2), 1000i128);
+    assert_eq!(client.balance(&lender1), 0i128);
+    assert_eq!(client.balance(&lender2), 2000i128);
+}
+
+#[test]
+fn test_timelocked_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let deployer_client =
+        LiquidityPoolDeployerClient::new(&env, &env.register_contract(None, LiquidityPoolDeployer));
+    let admin = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token, token_client) = create_token_contract(&env, &token_admin);
+
+    let salt = BytesN::from_array(&env, &[0; 32]);
+    let (contract_id, _contract) = deployer_client.deploy(&admin, &salt, &token.address, &1u32);
+    let client = liquidity_pool::Client::new(&env, &contract_id);
+
+    client.add_lender(&admin, &lender);
+    token_client.mint(&lender, &1000i128);
+    client.deposit(&lender, &1000i128, &liquidity_pool::Tranche::Senior);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(liquidity_pool::WASM);
+    let id = client.upgrade(&new_wasm_hash);
+
+    assert!(client.try_execute_change(&id).is_err());
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + client.min_delay());
+    client.execute_change(&id);
+
+    assert_eq!(client.queued_changes(), vec![&env]);
+    assert_eq!(client.balance(&lender), 1000i128);
+    assert_eq!(client.schema_version(), 2u32);
+}
+
+#[test]
+fn test_timelocked_upgrade_to_another_build() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let deployer_client =
+        LiquidityPoolDeployerClient::new(&env, &env.register_contract(None, LiquidityPoolDeployer));
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token, _token_client) = create_token_contract(&env, &token_admin);
+
+    let salt = BytesN::from_array(&env, &[0; 32]);
+    let (contract_id, _contract) = deployer_client.deploy(&admin, &salt, &token.address, &1u32);
+    let client = liquidity_pool::Client::new(&env, &contract_id);
+
+    let new_wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(liquidity_pool_v0_1_0::WASM);
+    let id = client.upgrade(&new_wasm_hash);
+
+    assert_eq!(client.schema_version(), 2u32);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + client.min_delay());
+    client.execute_change(&id);
+
+    // The v0.1.0 build has no schema version, and finds the admin kept in
+    // storage when initialized again.
+    let upgraded_client = liquidity_pool_v0_1_0::Client::new(&env, &contract_id);
+
+    assert!(client.try_schema_version().is_err());
+    assert!(upgraded_client
+        .try_initialize(&admin, &token.address)
+        .is_err());
 }
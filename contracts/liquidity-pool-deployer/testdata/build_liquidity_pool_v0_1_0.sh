@@ -0,0 +1,22 @@
+#!/bin/sh
+# Builds liquidity_pool_v0_1_0.wasm, the pool contract as released at the
+# v0.1.0 tag, used by the tests to start from a pool in the version 1 layout.
+#
+# Usage: contracts/liquidity-pool-deployer/testdata/build_liquidity_pool_v0_1_0.sh
+set -e
+
+testdata=$(cd "$(dirname "$0")" && pwd)
+root=$(git -C "$testdata" rev-parse --show-toplevel)
+build=$(mktemp -d)
+
+trap 'git -C "$root" worktree remove --force "$build/src"; rm -rf "$build"' EXIT
+
+git -C "$root" worktree add --detach "$build/src" v0.1.0
+cargo build \
+    --manifest-path "$build/src/Cargo.toml" \
+    --target-dir "$build/target" \
+    --target wasm32-unknown-unknown \
+    --release \
+    -p liquidity-pool
+cp "$build/target/wasm32-unknown-unknown/release/liquidity_pool.wasm" \
+    "$testdata/liquidity_pool_v0_1_0.wasm"
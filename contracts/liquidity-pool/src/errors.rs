@@ -6,7 +6,6 @@ use soroban_sdk::contracterror;
 pub enum LPError {
     AlreadyInitialized = 1,
     AmountMustBePositive = 2,
-    AddressNotRegistered = 3,
     BalanceNotAvailableForAmountRequested = 4,
     BorrowerAlreadyRegistered = 5,
     BorrowerNotRegistered = 6,
@@ -16,10 +15,43 @@ pub enum LPError {
     LenderNotRegistered = 10,
     AdminNotFound = 11,
     TokenNotFound = 12,
-    LenderNotFoundInContributions = 13,
-    LenderBalanceNotFound = 14,
-    LenderNotFound = 15,
     LenderDisabled = 16,
-    BorrowerNotFound = 17,
     BorrowerDisabled = 18,
+    InterestRateNotFound = 19,
+    InvalidInterestRate = 20,
+    InvalidBorrowerTerms = 21,
+    LoanExceedsMaxPrincipal = 22,
+    LoanTenorExceeded = 23,
+    CreditLimitExceeded = 24,
+    MaxOpenLoansExceeded = 25,
+    InvalidLoanDuration = 26,
+    InvalidMaturityTerms = 27,
+    LoanNotActive = 28,
+    LoanNotDefaulted = 29,
+    RecoveryExceedsLoss = 30,
+    CollateralTokenNotAllowed = 31,
+    InsufficientCollateral = 32,
+    InvalidCollateralConfig = 33,
+    CollateralNotFound = 34,
+    PriceNotAvailable = 36,
+    LoanNotLiquidatable = 37,
+    InvalidRepaymentSchedule = 38,
+    InvalidProtocolFee = 39,
+    InvalidOriginationFee = 41,
+    InsufficientAllowance = 42,
+    InvalidExpirationLedger = 43,
+    WithdrawalAlreadyRequested = 44,
+    WithdrawalRequestNotFound = 45,
+    InvalidTermMultiplier = 46,
+    PositionNotFound = 47,
+    PositionNotMatured = 48,
+    SeniorRatioExceeded = 49,
+    InvalidTrancheConfig = 50,
+    ChangeNotFound = 51,
+    ChangeNotReady = 52,
+    PendingAdminNotFound = 53,
+    Unauthorized = 54,
+    OperationPaused = 55,
+    InvalidDelay = 56,
+    AlreadyMigrated = 57,
 }
@@ -1,57 +1,323 @@
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{Address, BytesN, Env, Symbol};
 
-pub(crate) fn initialize(env: &Env, admin: Address, token: Address) {
+use crate::types::{
+    BorrowerTerms, Collateral, CollateralConfig, DepositTerm, InterestRateModel, MaturityTerms,
+    Operation, OriginationFee, ParameterChange, RepaymentSplit, Role, Tranche,
+};
+
+pub(crate) fn initialize(env: &Env, admin: Address, token: Address, interest_rate: u32) {
     let topics = (Symbol::new(env, "initialize"), admin, token);
-    env.events().publish(topics, ());
+    env.events().publish(topics, interest_rate);
+}
+
+pub(crate) fn propose_admin(env: &Env, admin: Address, new_admin: Address) {
+    let topics = (Symbol::new(env, "propose_admin"), admin);
+    env.events().publish(topics, new_admin);
+}
+
+pub(crate) fn cancel_admin_proposal(env: &Env, admin: Address, new_admin: Address) {
+    let topics = (Symbol::new(env, "cancel_admin_proposal"), admin);
+    env.events().publish(topics, new_admin);
+}
+
+pub(crate) fn accept_admin(env: &Env, previous_admin: Address, admin: Address) {
+    let topics = (Symbol::new(env, "accept_admin"), previous_admin);
+    env.events().publish(topics, admin);
+}
+
+pub(crate) fn upgrade(env: &Env, admin: Address, new_wasm_hash: BytesN<32>) {
+    let topics = (Symbol::new(env, "upgrade"), admin);
+    env.events().publish(topics, new_wasm_hash);
+}
+
+pub(crate) fn migrate(env: &Env, admin: Address, schema_version: u32) {
+    let topics = (Symbol::new(env, "migrate"), admin);
+    env.events().publish(topics, schema_version);
+}
+
+pub(crate) fn queue_change(
+    env: &Env,
+    caller: Address,
+    id: u64,
+    change: ParameterChange,
+    execution_time: u64,
+) {
+    let topics = (Symbol::new(env, "queue_change"), caller, id);
+    env.events().publish(topics, (change, execution_time));
+}
+
+pub(crate) fn execute_change(env: &Env, id: u64, change: ParameterChange) {
+    let topics = (Symbol::new(env, "execute_change"), id);
+    env.events().publish(topics, change);
+}
+
+pub(crate) fn cancel_change(env: &Env, caller: Address, id: u64, change: ParameterChange) {
+    let topics = (Symbol::new(env, "cancel_change"), caller, id);
+    env.events().publish(topics, change);
+}
+
+pub(crate) fn set_min_delay(env: &Env, admin: Address, delay: u64) {
+    let topics = (Symbol::new(env, "set_min_delay"), admin);
+    env.events().publish(topics, delay);
+}
+
+pub(crate) fn grant_role(env: &Env, admin: Address, role: Role, address: Address) {
+    let topics = (Symbol::new(env, "grant_role"), admin, address);
+    env.events().publish(topics, role);
+}
+
+pub(crate) fn revoke_role(env: &Env, admin: Address, role: Role, address: Address) {
+    let topics = (Symbol::new(env, "revoke_role"), admin, address);
+    env.events().publish(topics, role);
 }
 
-pub(crate) fn deposit(env: &Env, from: Address, amount: i128) {
-    let topics = (Symbol::new(env, "deposit"), from);
+pub(crate) fn pause(env: &Env, caller: Address, operation: Operation) {
+    let topics = (Symbol::new(env, "pause"), caller);
+    env.events().publish(topics, operation);
+}
+
+pub(crate) fn unpause(env: &Env, caller: Address, operation: Operation) {
+    let topics = (Symbol::new(env, "unpause"), caller);
+    env.events().publish(topics, operation);
+}
+
+pub(crate) fn deposit(env: &Env, from: Address, tranche: Tranche, amount: i128) {
+    let topics = (Symbol::new(env, "deposit"), from, tranche);
     env.events().publish(topics, amount);
 }
 
-pub(crate) fn withdraw(env: &Env, to: Address, amount: i128) {
-    let topics = (Symbol::new(env, "withdraw"), to);
+pub(crate) fn deposit_locked(
+    env: &Env,
+    from: Address,
+    position_id: u64,
+    amount: i128,
+    term: DepositTerm,
+) {
+    let topics = (Symbol::new(env, "deposit_locked"), from, position_id);
+    env.events().publish(topics, (amount, term));
+}
+
+pub(crate) fn withdraw_matured(
+    env: &Env,
+    to: Address,
+    position_id: u64,
+    amount: i128,
+    boost: i128,
+) {
+    let topics = (Symbol::new(env, "withdraw_matured"), to, position_id);
+    env.events().publish(topics, (amount, boost));
+}
+
+pub(crate) fn withdraw(env: &Env, to: Address, tranche: Tranche, amount: i128) {
+    let topics = (Symbol::new(env, "withdraw"), to, tranche);
     env.events().publish(topics, amount);
 }
 
-pub(crate) fn loan(env: &Env, to: Address, loan_id: u64, amount: i128) {
+pub(crate) fn request_withdrawal(
+    env: &Env,
+    lender: Address,
+    request_id: u64,
+    tranche: Tranche,
+    amount: i128,
+    shares: i128,
+) {
+    let topics = (Symbol::new(env, "request_withdrawal"), lender, request_id);
+    env.events().publish(topics, (tranche, amount, shares));
+}
+
+pub(crate) fn cancel_withdrawal(env: &Env, lender: Address, request_id: u64, shares: i128) {
+    let topics = (Symbol::new(env, "cancel_withdrawal"), lender, request_id);
+    env.events().publish(topics, shares);
+}
+
+pub(crate) fn fill_withdrawal(
+    env: &Env,
+    lender: Address,
+    request_id: u64,
+    amount: i128,
+    shares: i128,
+) {
+    let topics = (Symbol::new(env, "fill_withdrawal"), lender, request_id);
+    env.events().publish(topics, (amount, shares));
+}
+
+pub(crate) fn loan(env: &Env, to: Address, loan_id: u64, amount: i128, origination_fee: i128) {
     let topics = (Symbol::new(env, "loan"), to, loan_id);
-    env.events().publish(topics, amount);
+    env.events().publish(topics, (amount, origination_fee));
 }
 
-pub(crate) fn repay_loan(env: &Env, to: Address, loan_id: u64, amount: i128) {
+pub(crate) fn repay_loan(env: &Env, to: Address, loan_id: u64, split: RepaymentSplit) {
     let topics = (Symbol::new(env, "repay_loan"), to, loan_id);
+    env.events().publish(topics, split);
+}
+
+pub(crate) fn clear_repaid_loans(env: &Env, borrower: Address, cleared: u32) {
+    let topics = (Symbol::new(env, "clear_repaid_loans"), borrower);
+    env.events().publish(topics, cleared);
+}
+
+pub(crate) fn set_interest_rate(env: &Env, caller: Address, interest_rate: u32) {
+    let topics = (Symbol::new(env, "set_interest_rate"), caller);
+    env.events().publish(topics, interest_rate);
+}
+
+pub(crate) fn set_interest_rate_model(env: &Env, caller: Address, model: InterestRateModel) {
+    let topics = (Symbol::new(env, "set_interest_rate_model"), caller);
+    env.events().publish(topics, model);
+}
+
+pub(crate) fn set_max_open_loans(env: &Env, caller: Address, max_open_loans: Option<u32>) {
+    let topics = (Symbol::new(env, "set_max_open_loans"), caller);
+    env.events().publish(topics, max_open_loans);
+}
+
+pub(crate) fn set_origination_fee(env: &Env, caller: Address, origination_fee: OriginationFee) {
+    let topics = (Symbol::new(env, "set_origination_fee"), caller);
+    env.events().publish(topics, origination_fee);
+}
+
+pub(crate) fn set_term_multiplier(env: &Env, caller: Address, term: DepositTerm, multiplier: u32) {
+    let topics = (Symbol::new(env, "set_term_multiplier"), caller);
+    env.events().publish(topics, (term, multiplier));
+}
+
+pub(crate) fn set_max_senior_ratio(env: &Env, caller: Address, ratio: u32) {
+    let topics = (Symbol::new(env, "set_max_senior_ratio"), caller);
+    env.events().publish(topics, ratio);
+}
+
+pub(crate) fn set_junior_premium(env: &Env, caller: Address, premium: u32) {
+    let topics = (Symbol::new(env, "set_junior_premium"), caller);
+    env.events().publish(topics, premium);
+}
+
+pub(crate) fn set_protocol_fee(env: &Env, caller: Address, protocol_fee: u32) {
+    let topics = (Symbol::new(env, "set_protocol_fee"), caller);
+    env.events().publish(topics, protocol_fee);
+}
+
+pub(crate) fn set_treasury(env: &Env, caller: Address, treasury: Address) {
+    let topics = (Symbol::new(env, "set_treasury"), caller);
+    env.events().publish(topics, treasury);
+}
+
+pub(crate) fn claim_protocol_fees(env: &Env, caller: Address, treasury: Address, amount: i128) {
+    let topics = (Symbol::new(env, "claim_protocol_fees"), caller, treasury);
     env.events().publish(topics, amount);
 }
 
-pub(crate) fn add_borrower(env: &Env, admin: Address, borrower: Address) {
-    let topics = (Symbol::new(env, "add_borrower"), admin, borrower);
+pub(crate) fn set_max_price_age(env: &Env, caller: Address, max_age: u64) {
+    let topics = (Symbol::new(env, "set_max_price_age"), caller);
+    env.events().publish(topics, max_age);
+}
+
+pub(crate) fn set_oracle(env: &Env, caller: Address, oracle: Address) {
+    let topics = (Symbol::new(env, "set_oracle"), caller);
+    env.events().publish(topics, oracle);
+}
+
+pub(crate) fn set_maturity_terms(env: &Env, caller: Address, terms: MaturityTerms) {
+    let topics = (Symbol::new(env, "set_maturity_terms"), caller);
+    env.events().publish(topics, terms);
+}
+
+pub(crate) fn mark_default(
+    env: &Env,
+    caller: Address,
+    borrower: Address,
+    loan_id: u64,
+    loss: i128,
+) {
+    let topics = (Symbol::new(env, "mark_default"), caller, borrower, loan_id);
+    env.events().publish(topics, loss);
+}
+
+pub(crate) fn recover(env: &Env, caller: Address, borrower: Address, loan_id: u64, amount: i128) {
+    let topics = (Symbol::new(env, "recover"), caller, borrower, loan_id);
+    env.events().publish(topics, amount);
+}
+
+pub(crate) fn liquidate(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    loan_id: u64,
+    repay_amount: i128,
+    seized_amount: i128,
+) {
+    let topics = (Symbol::new(env, "liquidate"), liquidator, borrower, loan_id);
+    env.events().publish(topics, (repay_amount, seized_amount));
+}
+
+pub(crate) fn seize_collateral(
+    env: &Env,
+    caller: Address,
+    borrower: Address,
+    loan_id: u64,
+    collateral: Collateral,
+    amount: i128,
+) {
+    let topics = (
+        Symbol::new(env, "seize_collateral"),
+        caller,
+        borrower,
+        loan_id,
+    );
+    env.events().publish(topics, (collateral, amount));
+}
+
+pub(crate) fn set_collateral_token(
+    env: &Env,
+    caller: Address,
+    token: Address,
+    config: CollateralConfig,
+) {
+    let topics = (Symbol::new(env, "set_collateral_token"), caller, token);
+    env.events().publish(topics, config);
+}
+
+pub(crate) fn remove_collateral_token(env: &Env, caller: Address, token: Address) {
+    let topics = (Symbol::new(env, "remove_collateral_token"), caller, token);
     env.events().publish(topics, ());
 }
 
-pub(crate) fn set_borrower_status(env: &Env, admin: Address, borrower: Address, active: bool) {
+pub(crate) fn add_borrower(env: &Env, caller: Address, borrower: Address) {
+    let topics = (Symbol::new(env, "add_borrower"), caller, borrower);
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn set_borrower_status(env: &Env, caller: Address, borrower: Address, active: bool) {
     let topics: (Symbol, Address, Address) =
-        (Symbol::new(env, "set_borrower_status"), admin, borrower);
+        (Symbol::new(env, "set_borrower_status"), caller, borrower);
     env.events().publish(topics, active);
 }
 
-pub(crate) fn remove_borrower(env: &Env, admin: Address, borrower: Address) {
-    let topics = (Symbol::new(env, "remove_borrower"), admin, borrower);
+pub(crate) fn update_borrower_terms(
+    env: &Env,
+    caller: Address,
+    borrower: Address,
+    terms: BorrowerTerms,
+) {
+    let topics = (Symbol::new(env, "update_borrower_terms"), caller, borrower);
+    env.events().publish(topics, terms);
+}
+
+pub(crate) fn remove_borrower(env: &Env, caller: Address, borrower: Address) {
+    let topics = (Symbol::new(env, "remove_borrower"), caller, borrower);
     env.events().publish(topics, ());
 }
 
-pub(crate) fn add_lender(env: &Env, admin: Address, lender: Address) {
-    let topics = (Symbol::new(env, "add_lender"), admin, lender);
+pub(crate) fn add_lender(env: &Env, caller: Address, lender: Address) {
+    let topics = (Symbol::new(env, "add_lender"), caller, lender);
     env.events().publish(topics, ());
 }
 
-pub(crate) fn set_lender_status(env: &Env, admin: Address, lender: Address, active: bool) {
-    let topics = (Symbol::new(env, "set_lender_status"), admin, lender);
+pub(crate) fn set_lender_status(env: &Env, caller: Address, lender: Address, active: bool) {
+    let topics = (Symbol::new(env, "set_lender_status"), caller, lender);
     env.events().publish(topics, active);
 }
 
-pub(crate) fn remove_lender(env: &Env, admin: Address, lender: Address) {
-    let topics = (Symbol::new(env, "remove_lender"), admin, lender);
+pub(crate) fn remove_lender(env: &Env, caller: Address, lender: Address) {
+    let topics = (Symbol::new(env, "remove_lender"), caller, lender);
     env.events().publish(topics, ());
 }
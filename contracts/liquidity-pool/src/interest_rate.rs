@@ -0,0 +1,36 @@
+use crate::errors::LPError;
+use crate::types::InterestRateModel;
+
+/// Denominator of the rates, fees and ratios expressed in basis points.
+pub(crate) const BASIS_POINTS: i128 = 10_000;
+
+pub fn check_interest_rate_model(model: &InterestRateModel) -> Result<(), LPError> {
+    let max_rate = model.base_rate as i128 + model.slope1 as i128 + model.slope2 as i128;
+
+    if model.kink == 0 || model.kink as i128 > BASIS_POINTS || max_rate > BASIS_POINTS {
+        return Err(LPError::InvalidInterestRate);
+    }
+
+    Ok(())
+}
+
+pub fn calculate_utilization(outstanding_principal: i128, total_balance: i128) -> u32 {
+    let total_liquidity = outstanding_principal + total_balance;
+
+    if total_liquidity <= 0 {
+        return 0;
+    }
+
+    (outstanding_principal * BASIS_POINTS / total_liquidity) as u32
+}
+
+pub fn calculate_borrow_rate(model: &InterestRateModel, utilization: u32) -> u32 {
+    if utilization <= model.kink {
+        return model.base_rate + model.slope1 * utilization / model.kink;
+    }
+
+    let excess_utilization = utilization - model.kink;
+    let excess_range = BASIS_POINTS as u32 - model.kink;
+
+    model.base_rate + model.slope1 + model.slope2 * excess_utilization / excess_range
+}
@@ -1,30 +1,281 @@
 use crate::errors::LPError;
-use soroban_sdk::{Address, Env};
+use crate::types::{
+    BorrowerTerms, Collateral, CollateralConfig, DepositTerm, InterestRateModel, LenderSummary,
+    Loan, LoanSchedule, LoanStatus, LockedPosition, MaturityTerms, Operation, OriginationFee,
+    PauseStatus, QueuedChange, RepaymentSchedule, Role, Tranche,
+};
+use soroban_sdk::{Address, BytesN, Env, Vec};
 
 pub trait LiquidityPoolTrait {
-    fn initialize(env: Env, admin: Address, token: Address) -> Result<(), LPError>;
+    fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        interest_rate: u32,
+    ) -> Result<(), LPError>;
 
-    fn balance(env: Env, lender: Address) -> Result<i128, LPError>;
+    fn admin(env: Env) -> Result<Address, LPError>;
 
-    fn deposit(env: Env, lender: Address, amount: i128) -> Result<(), LPError>;
+    fn pending_admin(env: Env) -> Option<Address>;
 
-    fn withdraw(env: Env, lender: Address, amount: i128) -> Result<(), LPError>;
+    fn propose_admin(env: Env, new_admin: Address) -> Result<u64, LPError>;
 
-    fn loan(env: Env, borrower: Address, amount: i128) -> Result<u64, LPError>;
+    fn cancel_admin_proposal(env: Env) -> Result<(), LPError>;
 
-    fn repay_loan(env: Env, borrower: Address, loan_id: u64, amount: i128) -> Result<(), LPError>;
+    fn accept_admin(env: Env) -> Result<(), LPError>;
+
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<u64, LPError>;
+
+    fn migrate(env: Env, lenders: Vec<Address>, borrowers: Vec<Address>) -> Result<(), LPError>;
+
+    fn finalize_migration(env: Env) -> Result<(), LPError>;
+
+    fn schema_version(env: Env) -> u32;
+
+    fn min_delay(env: Env) -> u64;
+
+    fn set_min_delay(env: Env, delay: u64) -> Result<u64, LPError>;
+
+    fn queued_changes(env: Env) -> Vec<QueuedChange>;
+
+    fn execute_change(env: Env, id: u64) -> Result<(), LPError>;
+
+    fn cancel_change(env: Env, caller: Address, id: u64) -> Result<(), LPError>;
+
+    fn grant_role(env: Env, role: Role, address: Address) -> Result<u64, LPError>;
+
+    fn revoke_role(env: Env, role: Role, address: Address) -> Result<(), LPError>;
+
+    fn has_role(env: Env, role: Role, address: Address) -> bool;
+
+    fn pause(env: Env, caller: Address, operation: Operation) -> Result<(), LPError>;
+
+    fn unpause(env: Env, caller: Address, operation: Operation) -> Result<(), LPError>;
+
+    fn pause_status(env: Env) -> PauseStatus;
+
+    fn deposit(env: Env, lender: Address, amount: i128, tranche: Tranche) -> Result<(), LPError>;
+
+    fn deposit_locked(
+        env: Env,
+        lender: Address,
+        amount: i128,
+        term: DepositTerm,
+    ) -> Result<u64, LPError>;
+
+    fn positions(env: Env, lender: Address) -> Vec<LockedPosition>;
+
+    fn withdraw_matured(env: Env, lender: Address, position_id: u64) -> Result<i128, LPError>;
+
+    fn withdraw(env: Env, lender: Address, amount: i128, tranche: Tranche) -> Result<(), LPError>;
+
+    fn redeem(env: Env, lender: Address, shares: i128, tranche: Tranche) -> Result<i128, LPError>;
+
+    fn request_withdrawal(
+        env: Env,
+        lender: Address,
+        amount: i128,
+        tranche: Tranche,
+    ) -> Result<u64, LPError>;
+
+    fn cancel_withdrawal(env: Env, lender: Address, tranche: Tranche) -> Result<(), LPError>;
+
+    fn process_withdrawals(env: Env, limit: u32) -> Result<i128, LPError>;
+
+    fn withdrawal_position(env: Env, lender: Address, tranche: Tranche) -> Result<u32, LPError>;
+
+    fn pending_withdrawal(env: Env, lender: Address, tranche: Tranche) -> i128;
+
+    fn total_assets(env: Env) -> i128;
+
+    fn tranche_assets(env: Env, tranche: Tranche) -> i128;
+
+    fn total_shares(env: Env, tranche: Tranche) -> i128;
+
+    fn shares(env: Env, lender: Address, tranche: Tranche) -> i128;
+
+    fn convert_to_shares(env: Env, amount: i128, tranche: Tranche) -> i128;
+
+    fn convert_to_assets(env: Env, shares: i128, tranche: Tranche) -> i128;
+
+    fn max_senior_ratio(env: Env) -> Option<u32>;
+
+    fn set_max_senior_ratio(env: Env, caller: Address, ratio: u32) -> Result<u64, LPError>;
+
+    fn junior_premium(env: Env) -> u32;
+
+    fn set_junior_premium(env: Env, caller: Address, premium: u32) -> Result<u64, LPError>;
+
+    fn loan(
+        env: Env,
+        borrower: Address,
+        amount: i128,
+        duration: u64,
+        collateral: Option<Collateral>,
+        schedule: Option<RepaymentSchedule>,
+    ) -> Result<u64, LPError>;
+
+    fn repay_loan(
+        env: Env,
+        borrower: Address,
+        loan_id: u64,
+        amount: i128,
+    ) -> Result<LoanStatus, LPError>;
+
+    fn repay_in_full(env: Env, borrower: Address, loan_id: u64) -> Result<i128, LPError>;
 
     fn repay_loan_amount(env: Env, borrower: Address, loan_id: u64) -> Result<i128, LPError>;
 
-    fn add_lender(env: Env, lender: Address) -> Result<(), LPError>;
+    fn clear_repaid_loans(env: Env, borrower: Address) -> Result<u32, LPError>;
+
+    fn loan_info(env: Env, borrower: Address, loan_id: u64) -> Result<Loan, LPError>;
+
+    fn loan_schedule(env: Env, borrower: Address, loan_id: u64) -> Result<LoanSchedule, LPError>;
+
+    fn loan_status(env: Env, borrower: Address, loan_id: u64) -> Result<LoanStatus, LPError>;
+
+    fn mark_default(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        loan_id: u64,
+    ) -> Result<(), LPError>;
+
+    fn recover(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        loan_id: u64,
+        amount: i128,
+    ) -> Result<(), LPError>;
+
+    fn lender_loss(env: Env, lender: Address) -> i128;
+
+    fn lender_summary(env: Env, lender: Address) -> Result<LenderSummary, LPError>;
+
+    fn loan_collateral(env: Env, borrower: Address, loan_id: u64) -> Option<Collateral>;
+
+    fn seize_collateral(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        loan_id: u64,
+    ) -> Result<i128, LPError>;
+
+    fn health_factor(env: Env, borrower: Address, loan_id: u64) -> Result<i128, LPError>;
+
+    fn liquidate(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        loan_id: u64,
+        repay_amount: i128,
+    ) -> Result<i128, LPError>;
+
+    fn origination_fee(env: Env) -> OriginationFee;
+
+    fn set_origination_fee(
+        env: Env,
+        caller: Address,
+        origination_fee: OriginationFee,
+    ) -> Result<u64, LPError>;
+
+    fn term_multiplier(env: Env, term: DepositTerm) -> u32;
+
+    fn set_term_multiplier(
+        env: Env,
+        caller: Address,
+        term: DepositTerm,
+        multiplier: u32,
+    ) -> Result<u64, LPError>;
+
+    fn protocol_fee(env: Env) -> u32;
+
+    fn set_protocol_fee(env: Env, caller: Address, protocol_fee: u32) -> Result<u64, LPError>;
+
+    fn treasury(env: Env) -> Result<Address, LPError>;
+
+    fn set_treasury(env: Env, caller: Address, treasury: Address) -> Result<u64, LPError>;
+
+    fn treasury_balance(env: Env) -> i128;
+
+    fn claim_protocol_fees(env: Env, caller: Address) -> Result<i128, LPError>;
+
+    fn oracle(env: Env) -> Option<Address>;
+
+    fn set_oracle(env: Env, caller: Address, oracle: Address) -> Result<u64, LPError>;
+
+    fn max_price_age(env: Env) -> u64;
+
+    fn set_max_price_age(env: Env, caller: Address, max_age: u64) -> Result<u64, LPError>;
+
+    fn collateral_config(env: Env, token: Address) -> Result<CollateralConfig, LPError>;
+
+    fn set_collateral_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+        config: CollateralConfig,
+    ) -> Result<u64, LPError>;
+
+    fn remove_collateral_token(env: Env, caller: Address, token: Address) -> Result<u64, LPError>;
+
+    fn maturity_terms(env: Env) -> MaturityTerms;
+
+    fn set_maturity_terms(env: Env, caller: Address, terms: MaturityTerms) -> Result<u64, LPError>;
+
+    fn interest_rate(env: Env) -> Result<u32, LPError>;
+
+    fn set_interest_rate(env: Env, caller: Address, interest_rate: u32) -> Result<u64, LPError>;
+
+    fn interest_rate_model(env: Env) -> Result<InterestRateModel, LPError>;
+
+    fn set_interest_rate_model(
+        env: Env,
+        caller: Address,
+        model: InterestRateModel,
+    ) -> Result<u64, LPError>;
+
+    fn utilization(env: Env) -> u32;
+
+    fn borrow_rate(env: Env) -> Result<u32, LPError>;
+
+    fn max_open_loans_per_borrower(env: Env) -> Option<u32>;
+
+    fn set_max_open_loans_per_borrower(
+        env: Env,
+        caller: Address,
+        max_open_loans: Option<u32>,
+    ) -> Result<u64, LPError>;
+
+    fn add_lender(env: Env, caller: Address, lender: Address) -> Result<(), LPError>;
+
+    fn set_lender_status(
+        env: Env,
+        caller: Address,
+        lender: Address,
+        active: bool,
+    ) -> Result<(), LPError>;
+
+    fn remove_lender(env: Env, caller: Address, lender: Address) -> Result<(), LPError>;
 
-    fn set_lender_status(env: Env, lender: Address, active: bool) -> Result<(), LPError>;
+    fn add_borrower(env: Env, caller: Address, borrower: Address) -> Result<(), LPError>;
 
-    fn remove_lender(env: Env, lender: Address) -> Result<(), LPError>;
+    fn set_borrower_status(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        active: bool,
+    ) -> Result<(), LPError>;
 
-    fn add_borrower(env: Env, borrower: Address) -> Result<(), LPError>;
+    fn update_borrower_terms(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        terms: BorrowerTerms,
+    ) -> Result<u64, LPError>;
 
-    fn set_borrower_status(env: Env, borrower: Address, active: bool) -> Result<(), LPError>;
+    fn available_credit(env: Env, borrower: Address) -> Result<Option<i128>, LPError>;
 
-    fn remove_borrower(env: Env, lender: Address) -> Result<(), LPError>;
+    fn remove_borrower(env: Env, caller: Address, lender: Address) -> Result<(), LPError>;
 }
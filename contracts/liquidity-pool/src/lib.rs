@@ -2,336 +2,1894 @@
 
 mod errors;
 mod event;
+mod interest_rate;
 mod interface;
-mod percentage;
+mod migration;
+mod oracle;
+mod positions;
+mod schedule;
+mod share_token;
+mod shares;
 mod storage;
 mod testutils;
+mod timelock;
+mod tranches;
 mod types;
+mod withdrawal_queue;
 
 use crate::errors::LPError;
+use crate::interest_rate::BASIS_POINTS;
+use crate::interest_rate::{
+    calculate_borrow_rate, calculate_utilization, check_interest_rate_model,
+};
 use crate::interface::LiquidityPoolTrait;
-use crate::percentage::{calculate_repayment_amount, process_lender_contribution};
+use crate::migration::{
+    migrate_borrower, migrate_contributions, migrate_lender, migrate_loans, migrate_settings,
+};
+use crate::oracle::{calculate_collateral_amount, calculate_collateral_value};
+use crate::positions::{
+    calculate_boost_weight, calculate_position_boost, distribute_boost, generate_position_id,
+    term_days,
+};
+use crate::schedule::{
+    apply_installment_payment, calculate_loan_schedule, check_repayment_schedule,
+    generate_installments, has_overdue_installment,
+};
+use crate::shares::{
+    burn_shares, calculate_lender_interest, calculate_lender_loss, calculate_total_assets,
+    calculate_tranche_assets, convert_to_assets, convert_to_shares, convert_to_shares_rounding_up,
+    distribute_income, mint_shares,
+};
 use crate::storage::{
-    check_admin, has_admin, has_borrower, has_lender, read_admin, read_borrower,
-    read_contract_balance, read_contributions, read_lender, read_loans, read_token,
-    remove_borrower, remove_lender, remove_lender_contribution, write_admin, write_borrower,
-    write_contract_balance, write_lender, write_lender_contribution, write_loans, write_token,
+    check_admin, check_role, has_admin, has_borrower, has_collateral_token, has_lender, has_role,
+    read_admin, read_boost_balance, read_boost_index, read_boost_weight, read_borrower,
+    read_collateral_token, read_contract_balance, read_interest_rate_model, read_junior_assets,
+    read_junior_premium, read_lender, read_loan_collateral, read_loans, read_locked_shares,
+    read_maturity_terms, read_max_open_loans, read_max_price_age, read_max_senior_ratio,
+    read_oracle, read_origination_fee, read_outstanding_principal, read_pause_status,
+    read_pending_admin, read_positions, read_protocol_fee, read_queued_shares, read_schema_version,
+    read_shares, read_term_multiplier, read_token, read_total_shares, read_treasury,
+    read_treasury_balance, read_withdrawal_queue_head, read_withdrawal_queue_tail,
+    read_withdrawal_request, remove_borrower, remove_collateral_token, remove_lender,
+    remove_loan_collateral, remove_pending_admin, remove_role, remove_withdrawal_request,
+    write_admin, write_boost_balance, write_boost_weight, write_borrower, write_collateral_token,
+    write_contract_balance, write_interest_rate_model, write_junior_assets, write_junior_premium,
+    write_lender, write_loan_collateral, write_loans, write_locked_shares, write_maturity_terms,
+    write_max_open_loans, write_max_price_age, write_max_senior_ratio, write_min_delay,
+    write_oracle, write_origination_fee, write_outstanding_principal, write_pause_status,
+    write_pending_admin, write_positions, write_protocol_fee, write_queued_shares, write_role,
+    write_schema_version, write_term_multiplier, write_token, write_treasury,
+    write_treasury_balance, write_withdrawal_queue_head, write_withdrawal_request,
+};
+use crate::timelock::{
+    calculate_min_delay, dequeue_change, enqueue_change, find_change, list_changes, required_role,
+    MIN_DELAY,
+};
+use crate::tranches::{
+    allocate_income, allocate_loss, allocate_recovery, calculate_junior_withdrawable,
+    check_senior_ratio,
+};
+use crate::types::{
+    Borrower, BorrowerTerms, Collateral, CollateralConfig, DepositTerm, InterestRateModel, Lender,
+    LenderSummary, Loan, LoanSchedule, LoanStatus, LockedPosition, MaturityTerms, Operation,
+    OriginationFee, OriginationFeeMode, ParameterChange, PauseStatus, QueuedChange,
+    RepaymentSchedule, RepaymentSplit, Role, Tranche,
 };
-use crate::types::{Lender, Loan};
+use crate::withdrawal_queue::{
+    calculate_available_balance, calculate_queue_position, dequeue_withdrawal, enqueue_withdrawal,
+    find_withdrawal_request,
+};
+
+use soroban_sdk::{
+    contract, contractimpl, contractmeta,
+    token::{self},
+    Address, BytesN, Env, String, Vec,
+};
+use soroban_token_sdk::{metadata::TokenMetadata, TokenUtils};
+
+fn token_transfer(env: &Env, from: &Address, to: &Address, amount: &i128) -> Result<(), LPError> {
+    let token_id = read_token(env)?;
+    let token = token::Client::new(env, &token_id);
+    token.transfer(from, to, amount);
+    Ok(())
+}
+
+fn collateral_transfer(env: &Env, collateral: &Collateral, from: &Address, to: &Address) {
+    let token = token::Client::new(env, &collateral.token);
+    token.transfer(from, to, &collateral.amount);
+}
+
+fn release_collateral(env: &Env, borrower: &Address, loan_id: u64) {
+    if let Some(collateral) = read_loan_collateral(env, borrower, loan_id) {
+        collateral_transfer(env, &collateral, &env.current_contract_address(), borrower);
+        remove_loan_collateral(env, borrower, loan_id);
+    }
+}
+
+const SECONDS_PER_DAY: u64 = 86400;
+const SCHEMA_VERSION: u32 = 2;
+const SHARE_NAME: &str = "Clear Liquidity Pool Share";
+const SHARE_SYMBOL: &str = "CLPS";
+
+fn accrue_interest(env: &Env, loan: &mut Loan) {
+    let days = (env.ledger().timestamp() - loan.last_accrual_time) / SECONDS_PER_DAY;
+    if days == 0 {
+        return;
+    }
+
+    let accrued_until = loan.last_accrual_time + days * SECONDS_PER_DAY;
+    loan.accrued_interest +=
+        loan.principal * (loan.interest_rate as u64 * days) as i128 / BASIS_POINTS;
+
+    let terms = read_maturity_terms(env);
+    let penalty_start =
+        (loan.due_time.saturating_add(terms.grace_period)).max(loan.last_accrual_time);
+
+    if accrued_until > penalty_start {
+        let penalty_days = (accrued_until - penalty_start) / SECONDS_PER_DAY;
+        loan.accrued_fees +=
+            loan.principal * (terms.penalty_rate as u64 * penalty_days) as i128 / BASIS_POINTS;
+    }
+
+    loan.last_accrual_time = accrued_until;
+}
+
+fn calculate_debt(env: &Env, loan: &Loan) -> i128 {
+    let mut loan = loan.clone();
+    accrue_interest(env, &mut loan);
+
+    loan.principal + loan.accrued_interest + loan.accrued_fees
+}
+
+fn process_withdrawal(
+    env: &Env,
+    tranche: Tranche,
+    address: &Address,
+    amount: i128,
+    shares: i128,
+) -> Result<(), LPError> {
+    check_not_paused(env, Operation::Withdraw)?;
+
+    let total_balance = read_contract_balance(env);
+
+    if shares > read_shares(env, tranche, address) {
+        return Err(LPError::InsufficientBalance);
+    }
+
+    if amount > calculate_available_balance(env) {
+        return Err(LPError::BalanceNotAvailableForAmountRequested);
+    }
+
+    burn_shares(env, tranche, address, shares)?;
+    token_transfer(env, &env.current_contract_address(), address, &amount)?;
+
+    write_contract_balance(env, &(total_balance - amount));
+    record_withdrawal(env, address, amount);
+
+    if tranche == Tranche::Junior {
+        write_junior_assets(env, &(read_junior_assets(env) - amount));
+        check_senior_ratio(env)?;
+    }
+
+    Ok(())
+}
+
+fn process_withdrawal_queue(env: &Env, limit: u32) -> Result<i128, LPError> {
+    let contract = env.current_contract_address();
+    let tail = read_withdrawal_queue_tail(env);
+    let mut head = read_withdrawal_queue_head(env);
+    let mut id = head;
+    let mut processed = 0;
+    let mut paid = 0;
+
+    while id < tail && processed < limit && read_contract_balance(env) > 0 {
+        processed += 1;
+        id += 1;
+
+        let Some(mut request) = read_withdrawal_request(env, id - 1) else {
+            if head == id - 1 {
+                head = id;
+            }
+            continue;
+        };
+
+        let tranche = request.tranche;
+        let total_balance = read_contract_balance(env);
+        let available = match tranche {
+            Tranche::Senior => total_balance,
+            Tranche::Junior => total_balance.min(calculate_junior_withdrawable(env)),
+        };
+
+        // Junior requests blocked by the maximum senior ratio keep their place
+        // without holding back the requests behind them.
+        if available <= 0 {
+            continue;
+        }
+
+        let value = convert_to_assets(env, tranche, request.shares);
+        let (amount, shares) = if value <= available {
+            (value, request.shares)
+        } else {
+            let shares = convert_to_shares(env, tranche, available);
+            (convert_to_assets(env, tranche, shares), shares)
+        };
+
+        if shares == 0 {
+            continue;
+        }
+
+        burn_shares(env, tranche, &contract, shares)?;
+        write_queued_shares(env, tranche, &(read_queued_shares(env, tranche) - shares));
+
+        if amount > 0 {
+            token_transfer(env, &contract, &request.lender, &amount)?;
+            write_contract_balance(env, &(total_balance - amount));
+            record_withdrawal(env, &request.lender, amount);
+
+            if tranche == Tranche::Junior {
+                write_junior_assets(env, &(read_junior_assets(env) - amount));
+            }
+        }
+
+        request.shares -= shares;
+
+        if request.shares > 0 {
+            write_withdrawal_request(env, &request);
+        } else {
+            remove_withdrawal_request(env, &request);
+
+            if head == request.id {
+                head = id;
+            }
+        }
+
+        paid += amount;
+        event::fill_withdrawal(env, request.lender, request.id, amount, shares);
+    }
+
+    write_withdrawal_queue_head(env, &head);
+
+    Ok(paid)
+}
+
+fn process_deposit(
+    env: &Env,
+    tranche: Tranche,
+    address: &Address,
+    amount: i128,
+) -> Result<i128, LPError> {
+    check_not_paused(env, Operation::Deposit)?;
+    check_nonnegative_amount(amount)?;
+
+    if !has_lender(env, address) {
+        return Err(LPError::LenderNotRegistered);
+    }
+
+    if !read_lender(env, address)?.active {
+        return Err(LPError::LenderDisabled);
+    }
+
+    if read_total_shares(env, tranche) > 0 && calculate_tranche_assets(env, tranche) <= 0 {
+        return Err(LPError::InvalidTrancheConfig);
+    }
+
+    let shares = convert_to_shares(env, tranche, amount);
+
+    token_transfer(env, address, &env.current_contract_address(), &amount)?;
+
+    let total_balance = read_contract_balance(env) + amount;
+
+    mint_shares(env, tranche, address, shares);
+    write_contract_balance(env, &total_balance);
+
+    let mut lender = read_lender(env, address)?;
+    lender.deposited += amount;
+    write_lender(env, address, &lender);
+
+    match tranche {
+        Tranche::Senior => check_senior_ratio(env)?,
+        Tranche::Junior => write_junior_assets(env, &(read_junior_assets(env) + amount)),
+    }
+
+    Ok(shares)
+}
+
+/// Books money recovered on a defaulted loan paid by `caller`, releasing the
+/// remaining collateral once the loss is fully recovered.
+fn process_recovery(
+    env: &Env,
+    caller: &Address,
+    borrower: &Address,
+    loans: &mut Vec<Loan>,
+    loan_index: u32,
+    mut loan: Loan,
+    amount: i128,
+) -> Result<(), LPError> {
+    token_transfer(env, caller, &env.current_contract_address(), &amount)?;
+
+    allocate_recovery(env, amount);
+
+    loan.principal -= amount;
+
+    if loan.principal > 0 {
+        loans.set(loan_index, loan);
+    } else {
+        release_collateral(env, borrower, loan.id);
+
+        loans.remove(loan_index);
+    }
+
+    let total_balance = read_contract_balance(env) + amount;
+
+    write_loans(env, borrower, loans);
+    write_contract_balance(env, &total_balance);
+
+    Ok(())
+}
+
+fn record_withdrawal(env: &Env, address: &Address, amount: i128) {
+    if let Ok(mut lender) = read_lender(env, address) {
+        lender.withdrawn += amount;
+        write_lender(env, address, &lender);
+    }
+}
+
+fn calculate_protocol_fees(env: &Env, amount: i128) -> i128 {
+    amount * read_protocol_fee(env) as i128 / BASIS_POINTS
+}
+
+fn process_repayment(
+    env: &Env,
+    from: &Address,
+    borrower: &Address,
+    loan_id: u64,
+    amount: i128,
+) -> Result<(Loan, RepaymentSplit), LPError> {
+    let mut loans = read_loans(env, borrower);
+    let (loan_index, mut loan) = find_loan_index(&loans, loan_id)?;
+
+    if loan.status != LoanStatus::Active {
+        return Err(LPError::LoanNotActive);
+    }
+
+    accrue_interest(env, &mut loan);
+
+    let amount = amount.min(loan.principal + loan.accrued_interest + loan.accrued_fees);
+    let fees = amount.min(loan.accrued_fees);
+    let interest = (amount - fees).min(loan.accrued_interest);
+    let principal = (amount - fees - interest).min(loan.principal);
+
+    loan.accrued_fees -= fees;
+    loan.accrued_interest -= interest;
+    loan.interest_paid += interest;
+    loan.principal -= principal;
+
+    let protocol_fees = calculate_protocol_fees(env, fees + interest);
+    let junior_income = allocate_income(env, fees + interest - protocol_fees);
+    let boost = distribute_boost(env, fees + interest - protocol_fees - junior_income);
+    let amount_for_lenders = amount - protocol_fees - boost;
+
+    distribute_income(
+        env,
+        Tranche::Senior,
+        fees + interest - protocol_fees - junior_income - boost,
+    );
+
+    token_transfer(env, from, &env.current_contract_address(), &amount)?;
+
+    apply_installment_payment(&mut loan.installments, interest + principal);
+
+    let total_balance = read_contract_balance(env) + amount_for_lenders;
+    let outstanding_principal = read_outstanding_principal(env) - principal;
+
+    if loan.principal + loan.accrued_interest + loan.accrued_fees > 0 {
+        loans.set(loan_index, loan.clone());
+    } else {
+        release_collateral(env, borrower, loan_id);
+
+        loan.status = LoanStatus::Repaid;
+        loans.set(loan_index, loan.clone());
+    }
+
+    write_loans(env, borrower, &loans);
+    write_contract_balance(env, &total_balance);
+    write_outstanding_principal(env, &outstanding_principal);
+    write_treasury_balance(env, &(read_treasury_balance(env) + protocol_fees));
+
+    let split = RepaymentSplit {
+        fees,
+        interest,
+        principal,
+    };
+
+    Ok((loan, split))
+}
+
+fn current_loan_status(env: &Env, loan: &Loan) -> LoanStatus {
+    let now = env.ledger().timestamp();
+
+    if loan.status == LoanStatus::Active
+        && (now > loan.due_time || has_overdue_installment(&loan.installments, now))
+    {
+        return LoanStatus::Overdue;
+    }
+
+    loan.status
+}
+
+fn find_loan(env: &Env, borrower: &Address, loan_id: u64) -> Result<Loan, LPError> {
+    read_loans(env, borrower)
+        .iter()
+        .find(|loan| loan.id == loan_id)
+        .ok_or(LPError::LoanNotFoundOrExists)
+}
+
+fn find_loan_index(loans: &Vec<Loan>, loan_id: u64) -> Result<(u32, Loan), LPError> {
+    loans
+        .iter()
+        .enumerate()
+        .find(|(_, loan)| loan.id == loan_id)
+        .map(|(index, loan)| (index as u32, loan))
+        .ok_or(LPError::LoanNotFoundOrExists)
+}
+
+fn generate_id(env: &Env, loans: &Vec<Loan>) -> u64 {
+    loop {
+        let new_id = env.prng().gen();
+        if !loans.iter().any(|loan| loan.id == new_id) {
+            return new_id;
+        }
+    }
+}
+
+fn check_change_role(env: &Env, caller: &Address, change: &ParameterChange) -> Result<(), LPError> {
+    match required_role(change) {
+        Some(role) => check_role(env, caller, role),
+        None => {
+            caller.require_auth();
+
+            if *caller != read_admin(env)? {
+                return Err(LPError::Unauthorized);
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn write_share_metadata(env: &Env, token: &Address) {
+    TokenUtils::new(env)
+        .metadata()
+        .set_metadata(&TokenMetadata {
+            decimal: token::Client::new(env, token).decimals(),
+            name: String::from_str(env, SHARE_NAME),
+            symbol: String::from_str(env, SHARE_SYMBOL),
+        });
+}
+
+fn queue_change(env: &Env, caller: &Address, change: ParameterChange) -> Result<u64, LPError> {
+    let queued = enqueue_change(env, caller, change);
+
+    event::queue_change(
+        env,
+        queued.caller,
+        queued.id,
+        queued.change,
+        queued.execution_time,
+    );
+    Ok(queued.id)
+}
+
+fn apply_change(env: &Env, queued: QueuedChange) -> Result<(), LPError> {
+    let caller = queued.caller;
+
+    match queued.change {
+        ParameterChange::InterestRate(interest_rate) => {
+            let mut model = read_interest_rate_model(env)?;
+            model.base_rate = interest_rate;
+            check_interest_rate_model(&model)?;
+
+            write_interest_rate_model(env, &model);
+            event::set_interest_rate(env, caller, interest_rate);
+        }
+        ParameterChange::InterestRateModel(model) => {
+            write_interest_rate_model(env, &model);
+            event::set_interest_rate_model(env, caller, model);
+        }
+        ParameterChange::MaxOpenLoans(max_open_loans) => {
+            write_max_open_loans(env, &max_open_loans);
+            event::set_max_open_loans(env, caller, max_open_loans);
+        }
+        ParameterChange::OriginationFee(origination_fee) => {
+            write_origination_fee(env, &origination_fee);
+            event::set_origination_fee(env, caller, origination_fee);
+        }
+        ParameterChange::TermMultiplier(term, multiplier) => {
+            write_term_multiplier(env, term, &multiplier);
+            event::set_term_multiplier(env, caller, term, multiplier);
+        }
+        ParameterChange::MaxSeniorRatio(ratio) => {
+            write_max_senior_ratio(env, &ratio);
+            event::set_max_senior_ratio(env, caller, ratio);
+        }
+        ParameterChange::JuniorPremium(premium) => {
+            write_junior_premium(env, &premium);
+            event::set_junior_premium(env, caller, premium);
+        }
+        ParameterChange::ProtocolFee(protocol_fee) => {
+            write_protocol_fee(env, &protocol_fee);
+            event::set_protocol_fee(env, caller, protocol_fee);
+        }
+        ParameterChange::Treasury(treasury) => {
+            write_treasury(env, &treasury);
+            event::set_treasury(env, caller, treasury);
+        }
+        ParameterChange::Oracle(oracle) => {
+            write_oracle(env, &oracle);
+            event::set_oracle(env, caller, oracle);
+        }
+        ParameterChange::MaxPriceAge(max_age) => {
+            write_max_price_age(env, &max_age);
+            event::set_max_price_age(env, caller, max_age);
+        }
+        ParameterChange::MaturityTerms(terms) => {
+            write_maturity_terms(env, &terms);
+            event::set_maturity_terms(env, caller, terms);
+        }
+        ParameterChange::CollateralToken(token, config) => {
+            write_collateral_token(env, &token, &config);
+            event::set_collateral_token(env, caller, token, config);
+        }
+        ParameterChange::RemoveCollateralToken(token) => {
+            remove_collateral_token(env, &token);
+            event::remove_collateral_token(env, caller, token);
+        }
+        ParameterChange::BorrowerTerms(address, terms) => {
+            let mut borrower = read_borrower(env, &address)?;
+            borrower.terms = terms.clone();
+
+            write_borrower(env, &address, &borrower);
+            event::update_borrower_terms(env, caller, address, terms);
+        }
+        ParameterChange::GrantRole(role, address) => {
+            write_role(env, role, &address);
+            event::grant_role(env, caller, role, address);
+        }
+        ParameterChange::MinDelay(delay) => {
+            write_min_delay(env, &delay);
+            event::set_min_delay(env, caller, delay);
+        }
+        ParameterChange::Upgrade(new_wasm_hash) => {
+            env.deployer()
+                .update_current_contract_wasm(new_wasm_hash.clone());
+            event::upgrade(env, caller, new_wasm_hash);
+        }
+        ParameterChange::ProposeAdmin(new_admin) => {
+            write_pending_admin(env, &new_admin);
+            event::propose_admin(env, caller, new_admin);
+        }
+    }
+
+    Ok(())
+}
+
+fn check_not_paused(env: &Env, operation: Operation) -> Result<(), LPError> {
+    let status = read_pause_status(env);
+    let paused = match operation {
+        Operation::Deposit => status.deposit,
+        Operation::Loan => status.loan,
+        Operation::Withdraw => status.withdraw,
+    };
+
+    if paused {
+        return Err(LPError::OperationPaused);
+    }
+
+    Ok(())
+}
+
+fn write_paused(env: &Env, operation: Operation, paused: bool) {
+    let mut status = read_pause_status(env);
+
+    match operation {
+        Operation::Deposit => status.deposit = paused,
+        Operation::Loan => status.loan = paused,
+        Operation::Withdraw => status.withdraw = paused,
+    }
+
+    write_pause_status(env, &status);
+}
+
+fn check_nonnegative_amount(amount: i128) -> Result<(), LPError> {
+    if amount < 0 {
+        return Err(LPError::AmountMustBePositive);
+    }
+
+    Ok(())
+}
+
+fn check_borrower_terms(terms: &BorrowerTerms) -> Result<(), LPError> {
+    if let Some(interest_rate) = terms.interest_rate {
+        if interest_rate as i128 > BASIS_POINTS {
+            return Err(LPError::InvalidBorrowerTerms);
+        }
+    }
+
+    if let Some(max_principal) = terms.max_principal {
+        check_nonnegative_amount(max_principal).map_err(|_| LPError::InvalidBorrowerTerms)?;
+    }
+
+    if let Some(credit_limit) = terms.credit_limit {
+        check_nonnegative_amount(credit_limit).map_err(|_| LPError::InvalidBorrowerTerms)?;
+    }
+
+    Ok(())
+}
+
+fn check_maturity_terms(terms: &MaturityTerms) -> Result<(), LPError> {
+    if terms.max_tenor == Some(0) || terms.penalty_rate as i128 > BASIS_POINTS {
+        return Err(LPError::InvalidMaturityTerms);
+    }
+
+    Ok(())
+}
+
+fn check_loan_terms(
+    env: &Env,
+    terms: &BorrowerTerms,
+    loans: &Vec<Loan>,
+    amount: i128,
+    duration: u64,
+) -> Result<(), LPError> {
+    if duration == 0 {
+        return Err(LPError::InvalidLoanDuration);
+    }
+
+    if let Some(max_open_loans) = read_max_open_loans(env) {
+        let open_loans = loans
+            .iter()
+            .filter(|loan| matches!(loan.status, LoanStatus::Active | LoanStatus::Defaulted))
+            .count();
+
+        if open_loans >= max_open_loans as usize {
+            return Err(LPError::MaxOpenLoansExceeded);
+        }
+    }
+
+    if let Some(max_principal) = terms.max_principal {
+        if amount > max_principal {
+            return Err(LPError::LoanExceedsMaxPrincipal);
+        }
+    }
+
+    let max_tenors = [terms.max_tenor, read_maturity_terms(env).max_tenor];
+    if max_tenors
+        .iter()
+        .flatten()
+        .any(|max_tenor| duration > *max_tenor)
+    {
+        return Err(LPError::LoanTenorExceeded);
+    }
+
+    if let Some(credit_limit) = terms.credit_limit {
+        if calculate_credit_used(env, loans) + amount > credit_limit {
+            return Err(LPError::CreditLimitExceeded);
+        }
+    }
+
+    Ok(())
+}
+
+fn check_collateral(env: &Env, collateral: &Collateral, amount: i128) -> Result<(), LPError> {
+    let config = read_collateral_token(env, &collateral.token)?;
+
+    check_nonnegative_amount(collateral.amount)?;
+
+    let value = calculate_collateral_value(env, collateral)?;
+
+    if value * config.ltv as i128 / BASIS_POINTS < amount {
+        return Err(LPError::InsufficientCollateral);
+    }
+
+    Ok(())
+}
+
+fn check_collateral_config(config: &CollateralConfig) -> Result<(), LPError> {
+    if config.ltv == 0
+        || config.ltv > config.liquidation_threshold
+        || config.liquidation_threshold as i128 > BASIS_POINTS
+        || config.liquidation_bonus as i128 > BASIS_POINTS
+    {
+        return Err(LPError::InvalidCollateralConfig);
+    }
+
+    Ok(())
+}
+
+fn calculate_health_factor(env: &Env, borrower: &Address, loan: &Loan) -> Result<i128, LPError> {
+    let collateral =
+        read_loan_collateral(env, borrower, loan.id).ok_or(LPError::CollateralNotFound)?;
+    let config = read_collateral_token(env, &collateral.token)?;
+
+    let debt = calculate_debt(env, loan);
+    if debt <= 0 {
+        return Ok(i128::MAX);
+    }
+
+    let value = calculate_collateral_value(env, &collateral)?;
+
+    Ok(value * config.liquidation_threshold as i128 / debt)
+}
+
+/// Debt of the active loans plus the unrecovered principal of the defaulted
+/// ones, which keeps counting against the borrower until it is recovered.
+fn calculate_credit_used(env: &Env, loans: &Vec<Loan>) -> i128 {
+    loans
+        .iter()
+        .map(|loan| match loan.status {
+            LoanStatus::Active => calculate_debt(env, &loan),
+            LoanStatus::Defaulted => loan.principal,
+            _ => 0,
+        })
+        .sum()
+}
+
+contractmeta!(
+    key = "Description",
+    val = "Liquidity pool for loans with a configurable daily interest rate"
+);
+
+#[contract]
+pub struct LiquidityPoolContract;
+
+#[contractimpl]
+impl LiquidityPoolTrait for LiquidityPoolContract {
+    fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        interest_rate: u32,
+    ) -> Result<(), LPError> {
+        if has_admin(&env) {
+            return Err(LPError::AlreadyInitialized);
+        }
+
+        let model = InterestRateModel {
+            base_rate: interest_rate,
+            slope1: 0,
+            kink: BASIS_POINTS as u32,
+            slope2: 0,
+        };
+        check_interest_rate_model(&model)?;
+
+        write_admin(&env, &admin);
+        write_treasury(&env, &admin);
+        write_token(&env, &token);
+        write_share_metadata(&env, &token);
+        write_interest_rate_model(&env, &model);
+        write_contract_balance(&env, &0i128);
+        write_schema_version(&env, &SCHEMA_VERSION);
+
+        event::initialize(&env, admin, token, interest_rate);
+        Ok(())
+    }
+
+    fn admin(env: Env) -> Result<Address, LPError> {
+        read_admin(&env)
+    }
+
+    fn pending_admin(env: Env) -> Option<Address> {
+        read_pending_admin(&env)
+    }
+
+    fn propose_admin(env: Env, new_admin: Address) -> Result<u64, LPError> {
+        let admin = check_admin(&env)?;
+
+        queue_change(&env, &admin, ParameterChange::ProposeAdmin(new_admin))
+    }
+
+    fn cancel_admin_proposal(env: Env) -> Result<(), LPError> {
+        let admin = check_admin(&env)?;
+
+        let new_admin = read_pending_admin(&env).ok_or(LPError::PendingAdminNotFound)?;
+
+        remove_pending_admin(&env);
+
+        event::cancel_admin_proposal(&env, admin, new_admin);
+        Ok(())
+    }
+
+    fn accept_admin(env: Env) -> Result<(), LPError> {
+        let new_admin = read_pending_admin(&env).ok_or(LPError::PendingAdminNotFound)?;
+        new_admin.require_auth();
+
+        let previous_admin = read_admin(&env)?;
+
+        write_admin(&env, &new_admin);
+        remove_pending_admin(&env);
+
+        event::accept_admin(&env, previous_admin, new_admin);
+        Ok(())
+    }
+
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<u64, LPError> {
+        let admin = check_admin(&env)?;
+
+        queue_change(&env, &admin, ParameterChange::Upgrade(new_wasm_hash))
+    }
+
+    fn migrate(env: Env, lenders: Vec<Address>, borrowers: Vec<Address>) -> Result<(), LPError> {
+        let admin = check_admin(&env)?;
+
+        if read_schema_version(&env) >= SCHEMA_VERSION {
+            return Err(LPError::AlreadyMigrated);
+        }
+
+        if read_interest_rate_model(&env).is_err() {
+            migrate_settings(&env, &admin);
+            write_share_metadata(&env, &read_token(&env)?);
+        }
+
+        for borrower in borrowers.iter() {
+            migrate_borrower(&env, &borrower);
+            migrate_loans(&env, &borrower);
+        }
+
+        for lender in lenders.iter() {
+            migrate_lender(&env, &lender, 0);
+        }
+
+        Ok(())
+    }
+
+    fn finalize_migration(env: Env) -> Result<(), LPError> {
+        let admin = check_admin(&env)?;
+
+        if read_schema_version(&env) >= SCHEMA_VERSION {
+            return Err(LPError::AlreadyMigrated);
+        }
+
+        migrate_contributions(&env);
+        write_schema_version(&env, &SCHEMA_VERSION);
+
+        event::migrate(&env, admin, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    fn schema_version(env: Env) -> u32 {
+        read_schema_version(&env)
+    }
+
+    fn min_delay(env: Env) -> u64 {
+        calculate_min_delay(&env)
+    }
+
+    fn set_min_delay(env: Env, delay: u64) -> Result<u64, LPError> {
+        let admin = check_admin(&env)?;
+
+        if delay < MIN_DELAY {
+            return Err(LPError::InvalidDelay);
+        }
+
+        queue_change(&env, &admin, ParameterChange::MinDelay(delay))
+    }
+
+    fn queued_changes(env: Env) -> Vec<QueuedChange> {
+        list_changes(&env)
+    }
+
+    fn execute_change(env: Env, id: u64) -> Result<(), LPError> {
+        let queued = find_change(&env, id)?;
+
+        if env.ledger().timestamp() < queued.execution_time {
+            return Err(LPError::ChangeNotReady);
+        }
+
+        dequeue_change(&env, id)?;
+
+        event::execute_change(&env, id, queued.change.clone());
+        apply_change(&env, queued)
+    }
+
+    fn cancel_change(env: Env, caller: Address, id: u64) -> Result<(), LPError> {
+        let queued = find_change(&env, id)?;
+
+        check_change_role(&env, &caller, &queued.change)?;
+        dequeue_change(&env, id)?;
+
+        event::cancel_change(&env, caller, id, queued.change);
+        Ok(())
+    }
+
+    fn grant_role(env: Env, role: Role, address: Address) -> Result<u64, LPError> {
+        let admin = check_admin(&env)?;
+
+        queue_change(&env, &admin, ParameterChange::GrantRole(role, address))
+    }
+
+    fn revoke_role(env: Env, role: Role, address: Address) -> Result<(), LPError> {
+        let admin = check_admin(&env)?;
+
+        remove_role(&env, role, &address);
+
+        event::revoke_role(&env, admin, role, address);
+        Ok(())
+    }
+
+    fn has_role(env: Env, role: Role, address: Address) -> bool {
+        read_admin(&env).is_ok_and(|admin| admin == address) || has_role(&env, role, &address)
+    }
+
+    fn pause(env: Env, caller: Address, operation: Operation) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Pauser)?;
+
+        write_paused(&env, operation, true);
+
+        event::pause(&env, caller, operation);
+        Ok(())
+    }
+
+    fn unpause(env: Env, caller: Address, operation: Operation) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Pauser)?;
+
+        write_paused(&env, operation, false);
+
+        event::unpause(&env, caller, operation);
+        Ok(())
+    }
+
+    fn pause_status(env: Env) -> PauseStatus {
+        read_pause_status(&env)
+    }
+
+    fn deposit(env: Env, address: Address, amount: i128, tranche: Tranche) -> Result<(), LPError> {
+        address.require_auth();
+
+        process_deposit(&env, tranche, &address, amount)?;
+
+        event::deposit(&env, address, tranche, amount);
+        Ok(())
+    }
+
+    fn deposit_locked(
+        env: Env,
+        address: Address,
+        amount: i128,
+        term: DepositTerm,
+    ) -> Result<u64, LPError> {
+        address.require_auth();
+
+        let shares = process_deposit(&env, Tranche::Senior, &address, amount)?;
+        let multiplier = read_term_multiplier(&env, term);
+        let mut positions = read_positions(&env, &address);
+        let start_time = env.ledger().timestamp();
+
+        let position = LockedPosition {
+            id: generate_position_id(&env, &positions),
+            term,
+            amount,
+            shares,
+            multiplier,
+            start_time,
+            maturity_time: start_time + term_days(term) * SECONDS_PER_DAY,
+            boost_index: read_boost_index(&env),
+            boost: 0,
+        };
+
+        positions.push_back(position.clone());
+
+        let locked_shares = read_locked_shares(&env, &address) + shares;
+        let boost_weight = read_boost_weight(&env) + calculate_boost_weight(shares, multiplier);
+
+        write_positions(&env, &address, &positions);
+        write_locked_shares(&env, &address, &locked_shares);
+        write_boost_weight(&env, &boost_weight);
+
+        event::deposit_locked(&env, address, position.id, amount, term);
+        Ok(position.id)
+    }
+
+    fn positions(env: Env, lender: Address) -> Vec<LockedPosition> {
+        let mut positions = Vec::new(&env);
+
+        for mut position in read_positions(&env, &lender).iter() {
+            position.boost = calculate_position_boost(&env, &position);
+            positions.push_back(position);
+        }
+
+        positions
+    }
+
+    fn withdraw_matured(env: Env, address: Address, position_id: u64) -> Result<i128, LPError> {
+        address.require_auth();
+
+        if !has_lender(&env, &address) {
+            return Err(LPError::LenderNotRegistered);
+        }
+
+        let mut positions = read_positions(&env, &address);
+        let index = positions
+            .iter()
+            .position(|position| position.id == position_id)
+            .ok_or(LPError::PositionNotFound)?;
+        let position = positions.get_unchecked(index as u32);
+
+        if env.ledger().timestamp() < position.maturity_time {
+            return Err(LPError::PositionNotMatured);
+        }
+
+        let boost = calculate_position_boost(&env, &position);
+        let locked_shares = read_locked_shares(&env, &address) - position.shares;
+        let boost_weight =
+            read_boost_weight(&env) - calculate_boost_weight(position.shares, position.multiplier);
+
+        write_locked_shares(&env, &address, &locked_shares);
+        write_boost_weight(&env, &boost_weight);
+
+        let amount = convert_to_assets(&env, Tranche::Senior, position.shares);
+        process_withdrawal(&env, Tranche::Senior, &address, amount, position.shares)?;
+
+        if boost > 0 {
+            token_transfer(&env, &env.current_contract_address(), &address, &boost)?;
+            write_boost_balance(&env, &(read_boost_balance(&env) - boost));
+
+            let mut lender = read_lender(&env, &address)?;
+            lender.interest_earned += boost;
+            write_lender(&env, &address, &lender);
+        }
+
+        positions.remove(index as u32);
+        write_positions(&env, &address, &positions);
+
+        event::withdraw_matured(&env, address, position_id, amount, boost);
+        Ok(amount + boost)
+    }
+
+    fn withdraw(env: Env, address: Address, amount: i128, tranche: Tranche) -> Result<(), LPError> {
+        address.require_auth();
+
+        if !has_lender(&env, &address) {
+            return Err(LPError::LenderNotRegistered);
+        }
+
+        check_nonnegative_amount(amount)?;
+
+        let shares = convert_to_shares_rounding_up(&env, tranche, amount);
+        process_withdrawal(&env, tranche, &address, amount, shares)?;
+
+        event::withdraw(&env, address, tranche, amount);
+        Ok(())
+    }
+
+    fn redeem(env: Env, address: Address, shares: i128, tranche: Tranche) -> Result<i128, LPError> {
+        address.require_auth();
+
+        if !has_lender(&env, &address) {
+            return Err(LPError::LenderNotRegistered);
+        }
+
+        check_nonnegative_amount(shares)?;
+
+        let amount = convert_to_assets(&env, tranche, shares);
+        process_withdrawal(&env, tranche, &address, amount, shares)?;
+
+        event::withdraw(&env, address, tranche, amount);
+        Ok(amount)
+    }
+
+    fn request_withdrawal(
+        env: Env,
+        address: Address,
+        amount: i128,
+        tranche: Tranche,
+    ) -> Result<u64, LPError> {
+        address.require_auth();
+
+        if !has_lender(&env, &address) {
+            return Err(LPError::LenderNotRegistered);
+        }
+
+        check_nonnegative_amount(amount)?;
+
+        let shares = convert_to_shares_rounding_up(&env, tranche, amount);
+        let request_id = enqueue_withdrawal(&env, tranche, &address, shares)?;
+
+        event::request_withdrawal(&env, address, request_id, tranche, amount, shares);
+        Ok(request_id)
+    }
+
+    fn cancel_withdrawal(env: Env, address: Address, tranche: Tranche) -> Result<(), LPError> {
+        address.require_auth();
+
+        let request = dequeue_withdrawal(&env, tranche, &address)?;
+
+        event::cancel_withdrawal(&env, address, request.id, request.shares);
+        Ok(())
+    }
+
+    fn process_withdrawals(env: Env, limit: u32) -> Result<i128, LPError> {
+        check_not_paused(&env, Operation::Withdraw)?;
+
+        process_withdrawal_queue(&env, limit)
+    }
+
+    fn withdrawal_position(env: Env, address: Address, tranche: Tranche) -> Result<u32, LPError> {
+        let request = find_withdrawal_request(&env, tranche, &address)?;
+
+        Ok(calculate_queue_position(&env, request.id))
+    }
+
+    fn pending_withdrawal(env: Env, address: Address, tranche: Tranche) -> i128 {
+        match find_withdrawal_request(&env, tranche, &address) {
+            Ok(request) => convert_to_assets(&env, tranche, request.shares),
+            Err(_) => 0,
+        }
+    }
+
+    fn total_assets(env: Env) -> i128 {
+        calculate_total_assets(&env)
+    }
+
+    fn tranche_assets(env: Env, tranche: Tranche) -> i128 {
+        calculate_tranche_assets(&env, tranche)
+    }
+
+    fn total_shares(env: Env, tranche: Tranche) -> i128 {
+        read_total_shares(&env, tranche)
+    }
+
+    fn shares(env: Env, lender: Address, tranche: Tranche) -> i128 {
+        read_shares(&env, tranche, &lender)
+    }
+
+    fn convert_to_shares(env: Env, amount: i128, tranche: Tranche) -> i128 {
+        convert_to_shares(&env, tranche, amount)
+    }
+
+    fn convert_to_assets(env: Env, shares: i128, tranche: Tranche) -> i128 {
+        convert_to_assets(&env, tranche, shares)
+    }
+
+    fn max_senior_ratio(env: Env) -> Option<u32> {
+        read_max_senior_ratio(&env)
+    }
+
+    fn set_max_senior_ratio(env: Env, caller: Address, ratio: u32) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        if ratio == 0 {
+            return Err(LPError::InvalidTrancheConfig);
+        }
+
+        queue_change(&env, &caller, ParameterChange::MaxSeniorRatio(ratio))
+    }
+
+    fn junior_premium(env: Env) -> u32 {
+        read_junior_premium(&env)
+    }
+
+    fn set_junior_premium(env: Env, caller: Address, premium: u32) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        if premium as i128 > BASIS_POINTS {
+            return Err(LPError::InvalidTrancheConfig);
+        }
+
+        queue_change(&env, &caller, ParameterChange::JuniorPremium(premium))
+    }
+
+    fn loan(
+        env: Env,
+        address: Address,
+        amount: i128,
+        duration: u64,
+        collateral: Option<Collateral>,
+        schedule: Option<RepaymentSchedule>,
+    ) -> Result<u64, LPError> {
+        address.require_auth();
+
+        check_not_paused(&env, Operation::Loan)?;
+        check_nonnegative_amount(amount)?;
+        if !has_borrower(&env, &address) {
+            return Err(LPError::BorrowerNotRegistered);
+        }
+
+        let borrower = read_borrower(&env, &address)?;
+        if !borrower.active {
+            return Err(LPError::BorrowerDisabled);
+        }
+
+        let mut loans = read_loans(&env, &address);
+
+        let origination_fee = read_origination_fee(&env);
+        let fee = amount * origination_fee.rate as i128 / BASIS_POINTS;
+        let principal = match origination_fee.mode {
+            OriginationFeeMode::Deducted => amount,
+            OriginationFeeMode::Financed => amount + fee,
+        };
+
+        check_loan_terms(&env, &borrower.terms, &loans, principal, duration)?;
+
+        if let Some(schedule) = &schedule {
+            check_repayment_schedule(schedule, duration)?;
+        }
+
+        let total_balance = read_contract_balance(&env);
+
+        if principal > calculate_available_balance(&env) {
+            return Err(LPError::BalanceNotAvailableForAmountRequested);
+        }
+
+        if let Some(collateral) = &collateral {
+            check_collateral(&env, collateral, principal)?;
+            collateral_transfer(&env, collateral, &address, &env.current_contract_address());
+        }
+
+        token_transfer(
+            &env,
+            &env.current_contract_address(),
+            &address,
+            &(principal - fee),
+        )?;
+
+        let outstanding_principal = read_outstanding_principal(&env) + principal;
+        let utilization = calculate_utilization(outstanding_principal, total_balance - principal);
+        let interest_rate = match borrower.terms.interest_rate {
+            Some(interest_rate) => interest_rate,
+            None => calculate_borrow_rate(&read_interest_rate_model(&env)?, utilization),
+        };
+
+        let start_time = env.ledger().timestamp();
+        let due_time = start_time
+            .checked_add(duration)
+            .ok_or(LPError::InvalidLoanDuration)?;
+
+        let new_loan = Loan {
+            id: generate_id(&env, &loans),
+            amount: principal,
+            principal,
+            origination_fee: fee,
+            interest_paid: 0,
+            accrued_interest: 0,
+            accrued_fees: 0,
+            start_time,
+            last_accrual_time: start_time,
+            due_time,
+            interest_rate,
+            status: LoanStatus::Active,
+            installments: match &schedule {
+                Some(schedule) => {
+                    generate_installments(&env, schedule, principal, interest_rate, start_time)
+                }
+                None => Vec::new(&env),
+            },
+        };
 
-use soroban_sdk::{
-    contract, contractimpl, contractmeta,
-    token::{self},
-    Address, Env, Map, Vec,
-};
+        loans.push_back(new_loan.clone());
 
-fn token_transfer(env: &Env, from: &Address, to: &Address, amount: &i128) -> Result<(), LPError> {
-    let token_id = read_token(env)?;
-    let token = token::Client::new(env, &token_id);
-    token.transfer(from, to, amount);
-    Ok(())
-}
+        let protocol_fees = calculate_protocol_fees(&env, fee);
 
-fn calculate_fees(env: &Env, loan: &Loan) -> i128 {
-    let now_ledger = env.ledger().timestamp();
-    let start_time = loan.start_time;
-    let interest_rate_per_day = 10;
-    let seconds_per_day = 86400;
+        let junior_income = allocate_income(&env, fee - protocol_fees);
+        distribute_income(&env, Tranche::Senior, fee - protocol_fees - junior_income);
 
-    let duration_days = (now_ledger - start_time) / seconds_per_day;
+        write_contract_balance(&env, &(total_balance - principal + fee - protocol_fees));
+        write_treasury_balance(&env, &(read_treasury_balance(&env) + protocol_fees));
+        write_outstanding_principal(&env, &outstanding_principal);
+        write_loans(&env, &address, &loans);
 
-    loan.amount * (interest_rate_per_day * duration_days) as i128 / 100_000
-}
+        if let Some(collateral) = &collateral {
+            write_loan_collateral(&env, &address, new_loan.id, collateral);
+        }
 
-fn generate_id(env: &Env, loans: &Vec<Loan>) -> u64 {
-    loop {
-        let new_id = env.prng().gen();
-        if !loans.iter().any(|loan| loan.id == new_id) {
-            return new_id;
+        event::loan(&env, address, new_loan.id, principal, fee);
+        Ok(new_loan.id)
+    }
+
+    fn repay_loan(
+        env: Env,
+        borrower: Address,
+        loan_id: u64,
+        amount: i128,
+    ) -> Result<LoanStatus, LPError> {
+        borrower.require_auth();
+
+        check_nonnegative_amount(amount)?;
+
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
         }
+
+        let (loan, split) = process_repayment(&env, &borrower, &borrower, loan_id, amount)?;
+
+        event::repay_loan(&env, borrower, loan_id, split);
+        Ok(current_loan_status(&env, &loan))
     }
-}
 
-fn check_nonnegative_amount(amount: i128) -> Result<(), LPError> {
-    if amount < 0 {
-        return Err(LPError::AmountMustBePositive);
+    fn repay_in_full(env: Env, borrower: Address, loan_id: u64) -> Result<i128, LPError> {
+        borrower.require_auth();
+
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
+        }
+
+        let loan = find_loan(&env, &borrower, loan_id)?;
+        let amount = calculate_debt(&env, &loan);
+
+        let (_, split) = process_repayment(&env, &borrower, &borrower, loan_id, amount)?;
+
+        event::repay_loan(&env, borrower, loan_id, split);
+        Ok(amount)
     }
 
-    Ok(())
-}
+    fn repay_loan_amount(env: Env, borrower: Address, loan_id: u64) -> Result<i128, LPError> {
+        borrower.require_auth();
 
-fn update_lender_balances(
-    env: &Env,
-    lenders: Vec<Address>,
-    new_lender_amounts: Map<Address, i128>,
-) -> Result<(), LPError> {
-    for lender in lenders.iter() {
-        match new_lender_amounts.try_get(lender.clone()) {
-            Ok(Some(new_lender_balance)) => {
-                let data = Lender {
-                    active: true,
-                    balance: new_lender_balance,
-                };
-
-                write_lender(env, &lender, &data);
-            }
-            Ok(None) => {
-                return Err(LPError::LenderNotFoundInContributions);
-            }
-            Err(_) => {
-                return Err(LPError::LenderNotFoundInContributions);
-            }
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
         }
+
+        let loan = find_loan(&env, &borrower, loan_id)?;
+
+        Ok(calculate_debt(&env, &loan))
     }
-    Ok(())
-}
 
-contractmeta!(
-    key = "Description",
-    val = "Liquidity pool for loans with a daily fee of 0.1%"
-);
+    fn clear_repaid_loans(env: Env, borrower: Address) -> Result<u32, LPError> {
+        borrower.require_auth();
 
-#[contract]
-pub struct LiquidityPoolContract;
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
+        }
 
-#[contractimpl]
-impl LiquidityPoolTrait for LiquidityPoolContract {
-    fn initialize(env: Env, admin: Address, token: Address) -> Result<(), LPError> {
-        if has_admin(&env) {
-            return Err(LPError::AlreadyInitialized);
+        let loans = read_loans(&env, &borrower);
+        let mut open_loans = Vec::new(&env);
+
+        for loan in loans.iter() {
+            if loan.status != LoanStatus::Repaid {
+                open_loans.push_back(loan);
+            }
         }
 
-        write_admin(&env, &admin);
-        write_token(&env, &token);
-        write_contract_balance(&env, &0i128);
+        let cleared = loans.len() - open_loans.len();
 
-        event::initialize(&env, admin, token);
-        Ok(())
+        write_loans(&env, &borrower, &open_loans);
+
+        event::clear_repaid_loans(&env, borrower, cleared);
+        Ok(cleared)
     }
 
-    fn balance(env: Env, address: Address) -> Result<i128, LPError> {
-        if address == read_admin(&env)? {
-            return Ok(read_contract_balance(&env));
-        };
+    fn loan_info(env: Env, borrower: Address, loan_id: u64) -> Result<Loan, LPError> {
+        let mut loan = find_loan(&env, &borrower, loan_id)?;
+        loan.status = current_loan_status(&env, &loan);
 
-        if has_lender(&env, &address) {
-            let lender = read_lender(&env, &address)?;
-            return Ok(lender.balance);
-        }
+        Ok(loan)
+    }
+
+    fn loan_schedule(env: Env, borrower: Address, loan_id: u64) -> Result<LoanSchedule, LPError> {
+        let mut loan = find_loan(&env, &borrower, loan_id)?;
+        accrue_interest(&env, &mut loan);
 
-        Err(LPError::AddressNotRegistered)
+        Ok(calculate_loan_schedule(
+            loan.installments,
+            loan.accrued_interest + loan.accrued_fees,
+            env.ledger().timestamp(),
+        ))
     }
 
-    fn deposit(env: Env, address: Address, amount: i128) -> Result<(), LPError> {
-        address.require_auth();
+    fn loan_status(env: Env, borrower: Address, loan_id: u64) -> Result<LoanStatus, LPError> {
+        let loan = find_loan(&env, &borrower, loan_id)?;
 
-        check_nonnegative_amount(amount)?;
+        Ok(current_loan_status(&env, &loan))
+    }
 
-        if !has_lender(&env, &address) {
-            return Err(LPError::LenderNotRegistered);
+    fn mark_default(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        loan_id: u64,
+    ) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
         }
 
-        let mut lender = read_lender(&env, &address)?;
-        if !lender.active {
-            return Err(LPError::LenderDisabled);
+        let mut loans = read_loans(&env, &borrower);
+        let (loan_index, mut loan) = find_loan_index(&loans, loan_id)?;
+
+        if loan.status != LoanStatus::Active {
+            return Err(LPError::LoanNotActive);
         }
 
-        token_transfer(&env, &address, &env.current_contract_address(), &amount)?;
+        allocate_loss(&env, loan.principal);
 
-        let mut total_balance = read_contract_balance(&env);
+        loan.status = LoanStatus::Defaulted;
+        loans.set(loan_index, loan.clone());
 
-        total_balance += amount;
-        lender.balance += amount;
+        let outstanding_principal = read_outstanding_principal(&env) - loan.principal;
 
-        write_contract_balance(&env, &total_balance);
-        write_lender(&env, &address, &lender);
+        write_loans(&env, &borrower, &loans);
+        write_outstanding_principal(&env, &outstanding_principal);
+
+        event::mark_default(&env, caller, borrower, loan_id, loan.principal);
+        Ok(())
+    }
+
+    fn recover(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        loan_id: u64,
+        amount: i128,
+    ) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        check_nonnegative_amount(amount)?;
+
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
+        }
+
+        let mut loans = read_loans(&env, &borrower);
+        let (loan_index, loan) = find_loan_index(&loans, loan_id)?;
 
-        let mut contributions = read_contributions(&env);
+        if loan.status != LoanStatus::Defaulted {
+            return Err(LPError::LoanNotDefaulted);
+        }
 
-        if !contributions.contains(address.clone()) {
-            contributions.push_back(address.clone());
-            write_lender_contribution(&env, contributions);
+        if amount > loan.principal {
+            return Err(LPError::RecoveryExceedsLoss);
         }
 
-        event::deposit(&env, address, amount);
+        process_recovery(
+            &env, &caller, &borrower, &mut loans, loan_index, loan, amount,
+        )?;
+
+        event::recover(&env, caller, borrower, loan_id, amount);
         Ok(())
     }
 
-    fn withdraw(env: Env, address: Address, amount: i128) -> Result<(), LPError> {
-        address.require_auth();
+    fn lender_loss(env: Env, lender: Address) -> i128 {
+        calculate_lender_loss(&env, &lender)
+    }
 
+    fn lender_summary(env: Env, address: Address) -> Result<LenderSummary, LPError> {
         if !has_lender(&env, &address) {
             return Err(LPError::LenderNotRegistered);
         }
 
-        check_nonnegative_amount(amount)?;
+        let lender = read_lender(&env, &address)?;
+        let boost: i128 = read_positions(&env, &address)
+            .iter()
+            .map(|position| calculate_position_boost(&env, &position))
+            .sum();
+        let value = convert_to_assets(
+            &env,
+            Tranche::Senior,
+            read_shares(&env, Tranche::Senior, &address),
+        ) + convert_to_assets(
+            &env,
+            Tranche::Junior,
+            read_shares(&env, Tranche::Junior, &address),
+        );
+
+        Ok(LenderSummary {
+            active: lender.active,
+            deposited: lender.deposited,
+            withdrawn: lender.withdrawn,
+            interest_earned: calculate_lender_interest(&env, &address) + boost,
+            losses: calculate_lender_loss(&env, &address),
+            value,
+        })
+    }
 
-        let mut total_balance = read_contract_balance(&env);
-        let mut lender = read_lender(&env, &address)?;
+    fn loan_collateral(env: Env, borrower: Address, loan_id: u64) -> Option<Collateral> {
+        read_loan_collateral(&env, &borrower, loan_id)
+    }
 
-        if amount > lender.balance {
-            return Err(LPError::InsufficientBalance);
+    fn seize_collateral(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        loan_id: u64,
+    ) -> Result<i128, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
         }
 
-        if amount > total_balance {
-            return Err(LPError::BalanceNotAvailableForAmountRequested);
+        let mut loans = read_loans(&env, &borrower);
+        let (loan_index, loan) = find_loan_index(&loans, loan_id)?;
+
+        if loan.status != LoanStatus::Defaulted {
+            return Err(LPError::LoanNotDefaulted);
         }
 
-        token_transfer(&env, &env.current_contract_address(), &address, &amount)?;
+        let mut collateral =
+            read_loan_collateral(&env, &borrower, loan_id).ok_or(LPError::CollateralNotFound)?;
+
+        let value = calculate_collateral_value(&env, &collateral)?;
+        let (amount, seized_amount) = if value <= loan.principal {
+            (value, collateral.amount)
+        } else {
+            let seized_amount =
+                calculate_collateral_amount(&env, &collateral.token, loan.principal)?
+                    .min(collateral.amount);
+            (loan.principal, seized_amount)
+        };
 
-        total_balance -= amount;
-        lender.balance -= amount;
+        let seized = Collateral {
+            token: collateral.token.clone(),
+            amount: seized_amount,
+        };
+        collateral_transfer(&env, &seized, &env.current_contract_address(), &caller);
 
-        write_contract_balance(&env, &total_balance);
-        write_lender(&env, &address, &lender);
+        collateral.amount -= seized_amount;
 
-        if lender.balance <= 0 {
-            remove_lender_contribution(&env, &address)?;
+        if collateral.amount > 0 {
+            write_loan_collateral(&env, &borrower, loan_id, &collateral);
+        } else {
+            remove_loan_collateral(&env, &borrower, loan_id);
         }
 
-        event::withdraw(&env, address, amount);
-        Ok(())
+        process_recovery(
+            &env, &caller, &borrower, &mut loans, loan_index, loan, amount,
+        )?;
+
+        event::seize_collateral(&env, caller, borrower, loan_id, seized, amount);
+        Ok(amount)
     }
 
-    fn loan(env: Env, address: Address, amount: i128) -> Result<u64, LPError> {
-        address.require_auth();
+    fn health_factor(env: Env, borrower: Address, loan_id: u64) -> Result<i128, LPError> {
+        let loan = find_loan(&env, &borrower, loan_id)?;
 
-        check_nonnegative_amount(amount)?;
-        if !has_borrower(&env, &address) {
-            return Err(LPError::BorrowerNotRegistered);
-        }
+        calculate_health_factor(&env, &borrower, &loan)
+    }
 
-        if !read_borrower(&env, &address)? {
-            return Err(LPError::BorrowerDisabled);
-        }
+    fn liquidate(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        loan_id: u64,
+        repay_amount: i128,
+    ) -> Result<i128, LPError> {
+        liquidator.require_auth();
 
-        let total_balance = read_contract_balance(&env);
+        check_nonnegative_amount(repay_amount)?;
 
-        if amount > total_balance {
-            return Err(LPError::BalanceNotAvailableForAmountRequested);
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
         }
 
-        token_transfer(&env, &env.current_contract_address(), &address, &amount)?;
+        let loan = find_loan(&env, &borrower, loan_id)?;
+
+        if loan.status != LoanStatus::Active {
+            return Err(LPError::LoanNotActive);
+        }
 
-        let lenders = read_contributions(&env);
+        if calculate_health_factor(&env, &borrower, &loan)? >= BASIS_POINTS {
+            return Err(LPError::LoanNotLiquidatable);
+        }
 
-        let (lender_contributions, new_lender_amounts) =
-            process_lender_contribution(&env, lenders.clone(), &amount, &total_balance)?;
+        let mut collateral =
+            read_loan_collateral(&env, &borrower, loan_id).ok_or(LPError::CollateralNotFound)?;
+        let config = read_collateral_token(&env, &collateral.token)?;
 
-        let mut loans = read_loans(&env, &address);
+        let repay_amount = repay_amount.min(calculate_debt(&env, &loan));
+        let seized_amount = (calculate_collateral_amount(&env, &collateral.token, repay_amount)?
+            * (BASIS_POINTS + config.liquidation_bonus as i128)
+            / BASIS_POINTS)
+            .min(collateral.amount);
 
-        let new_loan = Loan {
-            id: generate_id(&env, &loans),
-            amount,
-            start_time: env.ledger().timestamp(),
-            contributions: lender_contributions,
+        let seized = Collateral {
+            token: collateral.token.clone(),
+            amount: seized_amount,
         };
+        collateral_transfer(&env, &seized, &env.current_contract_address(), &liquidator);
+
+        collateral.amount -= seized_amount;
+        write_loan_collateral(&env, &borrower, loan_id, &collateral);
+
+        process_repayment(&env, &liquidator, &borrower, loan_id, repay_amount)?;
+
+        event::liquidate(
+            &env,
+            liquidator,
+            borrower,
+            loan_id,
+            repay_amount,
+            seized_amount,
+        );
+        Ok(seized_amount)
+    }
 
-        loans.push_back(new_loan.clone());
+    fn origination_fee(env: Env) -> OriginationFee {
+        read_origination_fee(&env)
+    }
 
-        update_lender_balances(&env, lenders, new_lender_amounts)?;
+    fn set_origination_fee(
+        env: Env,
+        caller: Address,
+        origination_fee: OriginationFee,
+    ) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
 
-        write_contract_balance(&env, &(total_balance - amount));
-        write_loans(&env, &address, &loans);
-        write_borrower(&env, &address, true);
+        if origination_fee.rate as i128 > BASIS_POINTS {
+            return Err(LPError::InvalidOriginationFee);
+        }
 
-        event::loan(&env, address, new_loan.id, amount);
-        Ok(new_loan.id)
+        queue_change(
+            &env,
+            &caller,
+            ParameterChange::OriginationFee(origination_fee),
+        )
     }
 
-    fn repay_loan(env: Env, borrower: Address, loan_id: u64, amount: i128) -> Result<(), LPError> {
-        borrower.require_auth();
+    fn term_multiplier(env: Env, term: DepositTerm) -> u32 {
+        read_term_multiplier(&env, term)
+    }
 
-        check_nonnegative_amount(amount)?;
+    fn set_term_multiplier(
+        env: Env,
+        caller: Address,
+        term: DepositTerm,
+        multiplier: u32,
+    ) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
 
-        if !has_borrower(&env, &borrower) {
-            return Err(LPError::BorrowerNotRegistered);
+        if (multiplier as i128) < BASIS_POINTS {
+            return Err(LPError::InvalidTermMultiplier);
         }
 
-        let mut loans = read_loans(&env, &borrower);
-        let (loan_index, mut loan) = loans
-            .iter()
-            .enumerate()
-            .find(|(_, loan)| loan.id == loan_id)
-            .map(|(index, loan)| (index, loan.clone()))
-            .ok_or(LPError::LoanNotFoundOrExists)?;
+        queue_change(
+            &env,
+            &caller,
+            ParameterChange::TermMultiplier(term, multiplier),
+        )
+    }
 
-        let admin = read_admin(&env)?;
-        let total_fees = calculate_fees(&env, &loan);
-        let admin_fees = total_fees / 10;
-        let amount_for_lenders = amount - admin_fees;
+    fn protocol_fee(env: Env) -> u32 {
+        read_protocol_fee(&env)
+    }
 
-        token_transfer(&env, &borrower, &env.current_contract_address(), &amount)?;
-        token_transfer(&env, &env.current_contract_address(), &admin, &admin_fees)?;
+    fn set_protocol_fee(env: Env, caller: Address, protocol_fee: u32) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::Treasurer)?;
 
-        for (address, percentage) in loan.contributions.iter() {
-            let mut lender = read_lender(&env, &address)?;
-            lender.balance += calculate_repayment_amount(amount_for_lenders, percentage);
-            write_lender(&env, &address, &lender);
+        if protocol_fee as i128 > BASIS_POINTS {
+            return Err(LPError::InvalidProtocolFee);
         }
 
-        let repay_loan_amount = loan.amount + calculate_fees(&env, &loan);
-        let mut total_balance = read_contract_balance(&env);
-        total_balance += amount;
+        queue_change(&env, &caller, ParameterChange::ProtocolFee(protocol_fee))
+    }
 
-        if (repay_loan_amount - amount) > 0 {
-            loan.amount = repay_loan_amount - amount;
-            loans.set(loan_index as u32, loan);
-        } else {
-            loans.remove(loan_index as u32);
-        }
+    fn treasury(env: Env) -> Result<Address, LPError> {
+        read_treasury(&env)
+    }
 
-        write_loans(&env, &borrower, &loans);
-        write_contract_balance(&env, &total_balance);
+    fn set_treasury(env: Env, caller: Address, treasury: Address) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::Treasurer)?;
 
-        event::repay_loan(&env, borrower, loan_id, amount);
-        Ok(())
+        queue_change(&env, &caller, ParameterChange::Treasury(treasury))
     }
 
-    fn repay_loan_amount(env: Env, borrower: Address, loan_id: u64) -> Result<i128, LPError> {
-        borrower.require_auth();
+    fn treasury_balance(env: Env) -> i128 {
+        read_treasury_balance(&env)
+    }
 
-        if !has_borrower(&env, &borrower) {
-            return Err(LPError::BorrowerNotRegistered);
+    fn claim_protocol_fees(env: Env, caller: Address) -> Result<i128, LPError> {
+        check_role(&env, &caller, Role::Treasurer)?;
+
+        let treasury = read_treasury(&env)?;
+        let amount = read_treasury_balance(&env);
+
+        token_transfer(&env, &env.current_contract_address(), &treasury, &amount)?;
+        write_treasury_balance(&env, &0i128);
+
+        event::claim_protocol_fees(&env, caller, treasury, amount);
+        Ok(amount)
+    }
+
+    fn oracle(env: Env) -> Option<Address> {
+        read_oracle(&env)
+    }
+
+    fn set_oracle(env: Env, caller: Address, oracle: Address) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        queue_change(&env, &caller, ParameterChange::Oracle(oracle))
+    }
+
+    fn max_price_age(env: Env) -> u64 {
+        read_max_price_age(&env)
+    }
+
+    fn set_max_price_age(env: Env, caller: Address, max_age: u64) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        queue_change(&env, &caller, ParameterChange::MaxPriceAge(max_age))
+    }
+
+    fn collateral_config(env: Env, token: Address) -> Result<CollateralConfig, LPError> {
+        read_collateral_token(&env, &token)
+    }
+
+    fn set_collateral_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+        config: CollateralConfig,
+    ) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        check_collateral_config(&config)?;
+
+        queue_change(
+            &env,
+            &caller,
+            ParameterChange::CollateralToken(token, config),
+        )
+    }
+
+    fn remove_collateral_token(env: Env, caller: Address, token: Address) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        if !has_collateral_token(&env, &token) {
+            return Err(LPError::CollateralTokenNotAllowed);
         }
 
-        let loans = read_loans(&env, &borrower);
-        let loan = loans
-            .iter()
-            .find(|loan| loan.id == loan_id)
-            .ok_or(LPError::LoanNotFoundOrExists)?;
+        queue_change(&env, &caller, ParameterChange::RemoveCollateralToken(token))
+    }
 
-        Ok(loan.amount + calculate_fees(&env, &loan))
+    fn maturity_terms(env: Env) -> MaturityTerms {
+        read_maturity_terms(&env)
     }
 
-    fn add_borrower(env: Env, borrower: Address) -> Result<(), LPError> {
-        let admin = check_admin(&env)?;
+    fn set_maturity_terms(env: Env, caller: Address, terms: MaturityTerms) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        check_maturity_terms(&terms)?;
+
+        queue_change(&env, &caller, ParameterChange::MaturityTerms(terms))
+    }
+
+    fn interest_rate(env: Env) -> Result<u32, LPError> {
+        let model = read_interest_rate_model(&env)?;
+        Ok(model.base_rate)
+    }
+
+    fn set_interest_rate(env: Env, caller: Address, interest_rate: u32) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        let mut model = read_interest_rate_model(&env)?;
+        model.base_rate = interest_rate;
+        check_interest_rate_model(&model)?;
+
+        queue_change(&env, &caller, ParameterChange::InterestRate(interest_rate))
+    }
+
+    fn interest_rate_model(env: Env) -> Result<InterestRateModel, LPError> {
+        read_interest_rate_model(&env)
+    }
+
+    fn set_interest_rate_model(
+        env: Env,
+        caller: Address,
+        model: InterestRateModel,
+    ) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        check_interest_rate_model(&model)?;
+
+        queue_change(&env, &caller, ParameterChange::InterestRateModel(model))
+    }
+
+    fn utilization(env: Env) -> u32 {
+        calculate_utilization(
+            read_outstanding_principal(&env),
+            read_contract_balance(&env),
+        )
+    }
+
+    fn borrow_rate(env: Env) -> Result<u32, LPError> {
+        let model = read_interest_rate_model(&env)?;
+        let utilization = Self::utilization(env);
+        Ok(calculate_borrow_rate(&model, utilization))
+    }
+
+    fn max_open_loans_per_borrower(env: Env) -> Option<u32> {
+        read_max_open_loans(&env)
+    }
+
+    fn set_max_open_loans_per_borrower(
+        env: Env,
+        caller: Address,
+        max_open_loans: Option<u32>,
+    ) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        queue_change(&env, &caller, ParameterChange::MaxOpenLoans(max_open_loans))
+    }
+
+    fn add_borrower(env: Env, caller: Address, borrower: Address) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Operator)?;
 
         if has_borrower(&env, &borrower) {
             return Err(LPError::BorrowerAlreadyRegistered);
         }
 
-        write_borrower(&env, &borrower, true);
+        let data = Borrower {
+            active: true,
+            terms: BorrowerTerms {
+                interest_rate: None,
+                max_principal: None,
+                max_tenor: None,
+                credit_limit: None,
+            },
+        };
+
+        write_borrower(&env, &borrower, &data);
 
-        event::add_borrower(&env, admin, borrower);
+        event::add_borrower(&env, caller, borrower);
         Ok(())
     }
 
-    fn set_borrower_status(env: Env, address: Address, active: bool) -> Result<(), LPError> {
-        let admin = check_admin(&env)?;
+    fn set_borrower_status(
+        env: Env,
+        caller: Address,
+        address: Address,
+        active: bool,
+    ) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Operator)?;
 
         if !has_borrower(&env, &address) {
             return Err(LPError::BorrowerNotRegistered);
         }
 
-        write_borrower(&env, &address, active);
+        let mut borrower = read_borrower(&env, &address)?;
+        borrower.active = active;
 
-        event::set_borrower_status(&env, admin, address, active);
+        write_borrower(&env, &address, &borrower);
+
+        event::set_borrower_status(&env, caller, address, active);
         Ok(())
     }
 
-    fn remove_borrower(env: Env, borrower: Address) -> Result<(), LPError> {
-        let admin = check_admin(&env)?;
+    fn update_borrower_terms(
+        env: Env,
+        caller: Address,
+        address: Address,
+        terms: BorrowerTerms,
+    ) -> Result<u64, LPError> {
+        check_role(&env, &caller, Role::RiskManager)?;
+
+        if !has_borrower(&env, &address) {
+            return Err(LPError::BorrowerNotRegistered);
+        }
+
+        check_borrower_terms(&terms)?;
+
+        queue_change(
+            &env,
+            &caller,
+            ParameterChange::BorrowerTerms(address, terms),
+        )
+    }
+
+    fn available_credit(env: Env, borrower: Address) -> Result<Option<i128>, LPError> {
+        if !has_borrower(&env, &borrower) {
+            return Err(LPError::BorrowerNotRegistered);
+        }
+
+        let terms = read_borrower(&env, &borrower)?.terms;
+        let loans = read_loans(&env, &borrower);
+
+        Ok(terms
+            .credit_limit
+            .map(|credit_limit| (credit_limit - calculate_credit_used(&env, &loans)).max(0)))
+    }
+
+    fn remove_borrower(env: Env, caller: Address, borrower: Address) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Operator)?;
 
         if !has_borrower(&env, &borrower) {
             return Err(LPError::BorrowerNotRegistered);
@@ -339,12 +1897,12 @@ impl LiquidityPoolTrait for LiquidityPoolContract {
 
         remove_borrower(&env, &borrower);
 
-        event::remove_borrower(&env, admin, borrower);
+        event::remove_borrower(&env, caller, borrower);
         Ok(())
     }
 
-    fn add_lender(env: Env, lender: Address) -> Result<(), LPError> {
-        let admin = check_admin(&env)?;
+    fn add_lender(env: Env, caller: Address, lender: Address) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Operator)?;
 
         if has_lender(&env, &lender) {
             return Err(LPError::LenderAlreadyRegistered);
@@ -352,17 +1910,25 @@ impl LiquidityPoolTrait for LiquidityPoolContract {
 
         let data = Lender {
             active: true,
-            balance: 0i128,
+            deposited: 0,
+            withdrawn: 0,
+            interest_earned: 0,
+            losses: 0,
         };
 
         write_lender(&env, &lender, &data);
 
-        event::add_lender(&env, admin, lender);
+        event::add_lender(&env, caller, lender);
         Ok(())
     }
 
-    fn set_lender_status(env: Env, address: Address, active: bool) -> Result<(), LPError> {
-        let admin = check_admin(&env)?;
+    fn set_lender_status(
+        env: Env,
+        caller: Address,
+        address: Address,
+        active: bool,
+    ) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Operator)?;
 
         if !has_lender(&env, &address) {
             return Err(LPError::LenderNotRegistered);
@@ -371,37 +1937,22 @@ impl LiquidityPoolTrait for LiquidityPoolContract {
         let mut lender = read_lender(&env, &address)?;
         lender.active = active;
 
-        let mut contributions = read_contributions(&env);
-
-        if lender.active {
-            if !contributions.contains(&address) {
-                contributions.push_back(address.clone());
-            }
-        } else if let Some(index) = contributions
-            .iter()
-            .position(|addr| addr == address.clone())
-        {
-            contributions.remove(index as u32);
-        }
-
         write_lender(&env, &address, &lender);
-        write_lender_contribution(&env, contributions);
 
-        event::set_lender_status(&env, admin, address, active);
+        event::set_lender_status(&env, caller, address, active);
         Ok(())
     }
 
-    fn remove_lender(env: Env, lender: Address) -> Result<(), LPError> {
-        let admin = check_admin(&env)?;
+    fn remove_lender(env: Env, caller: Address, lender: Address) -> Result<(), LPError> {
+        check_role(&env, &caller, Role::Operator)?;
 
         if !has_lender(&env, &lender) {
             return Err(LPError::LenderNotRegistered);
         }
 
         remove_lender(&env, &lender);
-        remove_lender_contribution(&env, &lender)?;
 
-        event::remove_lender(&env, admin, lender);
+        event::remove_lender(&env, caller, lender);
         Ok(())
     }
 }
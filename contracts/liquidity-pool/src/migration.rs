@@ -0,0 +1,143 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::interest_rate::BASIS_POINTS;
+use crate::shares::mint_shares;
+use crate::storage::{
+    read_legacy_borrower, read_legacy_contributions, read_legacy_lender, read_legacy_loans,
+    read_lender, read_outstanding_principal, remove_legacy_contributions, write_borrower,
+    write_interest_rate_model, write_lender, write_loans, write_outstanding_principal,
+    write_treasury,
+};
+use crate::types::{Borrower, BorrowerTerms, InterestRateModel, Lender, Loan, LoanStatus, Tranche};
+
+/// Daily rate, in basis points, charged on every loan by the legacy layout.
+const LEGACY_INTEREST_RATE: u32 = 1;
+
+/// Scale of the lender percentages kept in the legacy loans, standing for the
+/// whole loan.
+const LEGACY_PERCENTAGE_SCALE: i128 = 100 * 10_000_000;
+
+/// Writes the settings the legacy layout had no key for: a flat interest rate
+/// model at the legacy daily rate and the admin as treasury.
+pub fn migrate_settings(env: &Env, admin: &Address) {
+    let model = InterestRateModel {
+        base_rate: LEGACY_INTEREST_RATE,
+        slope1: 0,
+        kink: BASIS_POINTS as u32,
+        slope2: 0,
+    };
+
+    write_interest_rate_model(env, &model);
+    write_treasury(env, admin);
+}
+
+/// Rewrites a borrower record stored as a `bool` active flag into a
+/// `Borrower` with no individual credit terms.
+pub fn migrate_borrower(env: &Env, address: &Address) {
+    if let Some(active) = read_legacy_borrower(env, address) {
+        let borrower = Borrower {
+            active,
+            terms: BorrowerTerms {
+                interest_rate: None,
+                max_principal: None,
+                max_tenor: None,
+                credit_limit: None,
+            },
+        };
+
+        write_borrower(env, address, &borrower);
+    }
+}
+
+/// Rewrites the lenders still in the legacy contribution list, which a batch
+/// may have left out, then drops the list.
+pub fn migrate_contributions(env: &Env) {
+    for lender in read_legacy_contributions(env).iter() {
+        migrate_lender(env, &lender, 0);
+    }
+
+    remove_legacy_contributions(env);
+}
+
+/// Rewrites a lender record stored as an active flag and balance into a
+/// `Lender` holding senior shares, minted one to one for its balance plus
+/// `loan_claim`, its part of the legacy loans. Lenders already rewritten are
+/// left untouched.
+pub fn migrate_lender(env: &Env, address: &Address, loan_claim: i128) {
+    let Some(legacy_lender) = read_legacy_lender(env, address) else {
+        return;
+    };
+
+    let claim = legacy_lender.balance + loan_claim;
+    let lender = Lender {
+        active: legacy_lender.active,
+        deposited: claim,
+        withdrawn: 0,
+        interest_earned: 0,
+        losses: 0,
+    };
+
+    write_lender(env, address, &lender);
+
+    if claim > 0 {
+        mint_shares(env, Tranche::Senior, address, claim);
+    }
+}
+
+/// Credits a lender with its part of a legacy loan, as senior shares minted
+/// one to one. A lender still in the legacy layout is rewritten on the way.
+fn credit_loan_claim(env: &Env, address: &Address, loan_claim: i128) {
+    if read_legacy_lender(env, address).is_some() {
+        migrate_lender(env, address, loan_claim);
+        return;
+    }
+
+    if let Ok(mut lender) = read_lender(env, address) {
+        lender.deposited += loan_claim;
+        write_lender(env, address, &lender);
+
+        if loan_claim > 0 {
+            mint_shares(env, Tranche::Senior, address, loan_claim);
+        }
+    }
+}
+
+/// Rewrites the loans of a borrower stored in the legacy layout and adds their
+/// principal to the outstanding principal, which was not tracked back then.
+/// The part of each loan owed to the lenders that funded it is credited to
+/// them. Legacy loans had no due date, so they never become overdue.
+pub fn migrate_loans(env: &Env, address: &Address) {
+    let Some(legacy_loans) = read_legacy_loans(env, address) else {
+        return;
+    };
+
+    let mut loans = Vec::new(env);
+    let mut principal = 0;
+
+    for legacy_loan in legacy_loans.iter() {
+        for (lender, percentage) in legacy_loan.contributions.iter() {
+            let claim = legacy_loan.amount * percentage as i128 / LEGACY_PERCENTAGE_SCALE;
+            credit_loan_claim(env, &lender, claim);
+        }
+
+        loans.push_back(Loan {
+            id: legacy_loan.id,
+            amount: legacy_loan.amount,
+            principal: legacy_loan.amount,
+            origination_fee: 0,
+            interest_paid: 0,
+            accrued_interest: 0,
+            accrued_fees: 0,
+            start_time: legacy_loan.start_time,
+            last_accrual_time: legacy_loan.start_time,
+            due_time: u64::MAX,
+            interest_rate: LEGACY_INTEREST_RATE,
+            status: LoanStatus::Active,
+            installments: Vec::new(env),
+        });
+        principal += legacy_loan.amount;
+    }
+
+    write_loans(env, address, &loans);
+    write_outstanding_principal(env, &(read_outstanding_principal(env) + principal));
+}
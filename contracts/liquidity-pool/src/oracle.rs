@@ -0,0 +1,62 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+use crate::errors::LPError;
+use crate::storage::{read_max_price_age, read_oracle, read_token};
+use crate::types::Collateral;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn decimals(env: Env) -> u32;
+
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+/// Reads the last price of a token, which must be positive and not older than
+/// the maximum price age. No price is available until an oracle is set.
+pub fn read_price(env: &Env, token: &Address) -> Result<i128, LPError> {
+    let oracle = read_oracle(env).ok_or(LPError::PriceNotAvailable)?;
+    let oracle = PriceOracleClient::new(env, &oracle);
+    let now = env.ledger().timestamp();
+
+    match oracle.lastprice(&Asset::Stellar(token.clone())) {
+        Some(data)
+            if data.price > 0 && now.saturating_sub(data.timestamp) <= read_max_price_age(env) =>
+        {
+            Ok(data.price)
+        }
+        _ => Err(LPError::PriceNotAvailable),
+    }
+}
+
+pub fn calculate_collateral_value(env: &Env, collateral: &Collateral) -> Result<i128, LPError> {
+    let collateral_price = read_price(env, &collateral.token)?;
+    let token_price = read_price(env, &read_token(env)?)?;
+
+    Ok(collateral.amount * collateral_price / token_price)
+}
+
+pub fn calculate_collateral_amount(
+    env: &Env,
+    token: &Address,
+    value: i128,
+) -> Result<i128, LPError> {
+    let collateral_price = read_price(env, token)?;
+    let token_price = read_price(env, &read_token(env)?)?;
+
+    Ok(value * token_price / collateral_price)
+}
@@ -0,0 +1,56 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::interest_rate::BASIS_POINTS;
+use crate::storage::{
+    read_boost_balance, read_boost_index, read_boost_weight, read_total_shares,
+    write_boost_balance, write_boost_index,
+};
+use crate::types::{DepositTerm, LockedPosition, Tranche};
+
+pub(crate) const BOOST_INDEX_SCALE: i128 = 1_000_000_000_000;
+
+pub fn term_days(term: DepositTerm) -> u64 {
+    match term {
+        DepositTerm::Days30 => 30,
+        DepositTerm::Days90 => 90,
+        DepositTerm::Days180 => 180,
+    }
+}
+
+pub fn generate_position_id(env: &Env, positions: &Vec<LockedPosition>) -> u64 {
+    loop {
+        let new_id = env.prng().gen();
+        if !positions.iter().any(|position| position.id == new_id) {
+            return new_id;
+        }
+    }
+}
+
+/// Extra weight of a position in the interest distribution on top of its shares.
+pub fn calculate_boost_weight(shares: i128, multiplier: u32) -> i128 {
+    shares * (multiplier as i128 - BASIS_POINTS) / BASIS_POINTS
+}
+
+pub fn calculate_position_boost(env: &Env, position: &LockedPosition) -> i128 {
+    let weight = calculate_boost_weight(position.shares, position.multiplier);
+
+    position.boost + weight * (read_boost_index(env) - position.boost_index) / BOOST_INDEX_SCALE
+}
+
+/// Sets aside the part of the lenders' income owed to the locked positions and
+/// returns it, so that it does not go into the value of the shares.
+pub fn distribute_boost(env: &Env, income: i128) -> i128 {
+    let weight = read_boost_weight(env);
+
+    if weight <= 0 || income <= 0 {
+        return 0;
+    }
+
+    let boost = income * weight / (read_total_shares(env, Tranche::Senior) + weight);
+    let index = read_boost_index(env) + boost * BOOST_INDEX_SCALE / weight;
+
+    write_boost_index(env, &index);
+    write_boost_balance(env, &(read_boost_balance(env) + boost));
+
+    boost
+}
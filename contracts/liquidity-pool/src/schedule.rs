@@ -0,0 +1,112 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::errors::LPError;
+use crate::interest_rate::BASIS_POINTS;
+use crate::types::{Installment, LoanSchedule, RepaymentSchedule};
+use crate::SECONDS_PER_DAY;
+
+/// Largest number of installments of a loan, which are stored with the loan.
+pub(crate) const MAX_INSTALLMENTS: u32 = 120;
+
+pub fn check_repayment_schedule(
+    schedule: &RepaymentSchedule,
+    duration: u64,
+) -> Result<(), LPError> {
+    if schedule.installments == 0
+        || schedule.installments > MAX_INSTALLMENTS
+        || schedule.period == 0
+    {
+        return Err(LPError::InvalidRepaymentSchedule);
+    }
+
+    match (schedule.installments as u64).checked_mul(schedule.period) {
+        Some(schedule_duration) if schedule_duration <= duration => Ok(()),
+        _ => Err(LPError::InvalidRepaymentSchedule),
+    }
+}
+
+/// Splits the principal evenly between the installments and adds to each one
+/// the interest of its period on the principal still scheduled at its start.
+pub fn generate_installments(
+    env: &Env,
+    schedule: &RepaymentSchedule,
+    amount: i128,
+    interest_rate: u32,
+    start_time: u64,
+) -> Vec<Installment> {
+    let mut installments = Vec::new(env);
+    let installment_amount = amount / schedule.installments as i128;
+    let period_days = schedule.period / SECONDS_PER_DAY;
+    let mut remaining = amount;
+
+    for number in 1..=schedule.installments {
+        let principal = if number == schedule.installments {
+            remaining
+        } else {
+            installment_amount
+        };
+        let interest = remaining * (interest_rate as u64 * period_days) as i128 / BASIS_POINTS;
+        remaining -= principal;
+
+        installments.push_back(Installment {
+            due_time: start_time + schedule.period * number as u64,
+            amount: principal + interest,
+            paid: 0,
+        });
+    }
+
+    installments
+}
+
+/// Applies the interest and principal parts of a payment to the installments
+/// in order. Penalty fees settled by a payment do not count towards them.
+pub fn apply_installment_payment(installments: &mut Vec<Installment>, amount: i128) {
+    let mut remaining = amount;
+
+    for (index, mut installment) in installments.clone().iter().enumerate() {
+        if remaining <= 0 {
+            break;
+        }
+
+        let payment = (installment.amount - installment.paid).min(remaining);
+        installment.paid += payment;
+        remaining -= payment;
+
+        installments.set(index as u32, installment);
+    }
+}
+
+pub fn has_overdue_installment(installments: &Vec<Installment>, now: u64) -> bool {
+    installments
+        .iter()
+        .any(|installment| now > installment.due_time && installment.paid < installment.amount)
+}
+
+pub fn calculate_loan_schedule(
+    installments: Vec<Installment>,
+    accrued: i128,
+    now: u64,
+) -> LoanSchedule {
+    let mut paid = 0;
+    let mut due = 0;
+    let mut overdue = 0;
+
+    for installment in installments.iter() {
+        let unpaid = installment.amount - installment.paid;
+
+        paid += installment.paid;
+        if now > installment.due_time {
+            overdue += unpaid;
+        } else {
+            due += unpaid;
+        }
+    }
+
+    LoanSchedule {
+        installments,
+        paid,
+        due,
+        overdue,
+        accrued,
+    }
+}
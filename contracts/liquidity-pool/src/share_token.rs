@@ -0,0 +1,129 @@
+use soroban_sdk::{contractimpl, panic_with_error, token::TokenInterface, Address, Env, String};
+use soroban_token_sdk::TokenUtils;
+
+use crate::errors::LPError;
+use crate::shares::{burn_shares, transfer_shares};
+use crate::storage::{has_lender, read_allowance, read_shares, write_allowance};
+use crate::types::{Allowance, Tranche};
+use crate::{LiquidityPoolContract, LiquidityPoolContractClient};
+
+fn check_share_amount(env: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(env, LPError::AmountMustBePositive);
+    }
+}
+
+fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let mut allowance = read_allowance(env, from, spender);
+
+    if allowance.amount < amount {
+        panic_with_error!(env, LPError::InsufficientAllowance);
+    }
+
+    if amount > 0 {
+        allowance.amount -= amount;
+        write_allowance(env, from, spender, &allowance);
+    }
+}
+
+/// Shares can only be held by lenders, who are the ones allowed to redeem them.
+fn check_receiver(env: &Env, to: &Address) {
+    if !has_lender(env, to) {
+        panic_with_error!(env, LPError::LenderNotRegistered);
+    }
+}
+
+fn unwrap_or_panic(env: &Env, result: Result<(), LPError>) {
+    if let Err(error) = result {
+        panic_with_error!(env, error);
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for LiquidityPoolContract {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        read_allowance(&env, &from, &spender).amount
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+
+        check_share_amount(&env, amount);
+
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, LPError::InvalidExpirationLedger);
+        }
+
+        let allowance = Allowance {
+            amount,
+            expiration_ledger,
+        };
+        write_allowance(&env, &from, &spender, &allowance);
+
+        TokenUtils::new(&env)
+            .events()
+            .approve(from, spender, amount, expiration_ledger);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        read_shares(&env, Tranche::Senior, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+
+        check_share_amount(&env, amount);
+        check_receiver(&env, &to);
+        unwrap_or_panic(
+            &env,
+            transfer_shares(&env, Tranche::Senior, &from, &to, amount),
+        );
+
+        TokenUtils::new(&env).events().transfer(from, to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+
+        check_share_amount(&env, amount);
+        spend_allowance(&env, &from, &spender, amount);
+        check_receiver(&env, &to);
+        unwrap_or_panic(
+            &env,
+            transfer_shares(&env, Tranche::Senior, &from, &to, amount),
+        );
+
+        TokenUtils::new(&env).events().transfer(from, to, amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+
+        check_share_amount(&env, amount);
+        unwrap_or_panic(&env, burn_shares(&env, Tranche::Senior, &from, amount));
+
+        TokenUtils::new(&env).events().burn(from, amount);
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+
+        check_share_amount(&env, amount);
+        spend_allowance(&env, &from, &spender, amount);
+        unwrap_or_panic(&env, burn_shares(&env, Tranche::Senior, &from, amount));
+
+        TokenUtils::new(&env).events().burn(from, amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        TokenUtils::new(&env).metadata().get_metadata().decimal
+    }
+
+    fn name(env: Env) -> String {
+        TokenUtils::new(&env).metadata().get_metadata().name
+    }
+
+    fn symbol(env: Env) -> String {
+        TokenUtils::new(&env).metadata().get_metadata().symbol
+    }
+}
@@ -0,0 +1,173 @@
+use soroban_sdk::{Address, Env};
+
+use crate::errors::LPError;
+use crate::storage::{
+    read_contract_balance, read_income_checkpoint, read_income_index, read_junior_assets,
+    read_lender, read_locked_shares, read_loss_checkpoint, read_loss_index,
+    read_outstanding_principal, read_shares, read_total_shares, write_income_checkpoint,
+    write_income_index, write_lender, write_loss_checkpoint, write_loss_index, write_shares,
+    write_total_shares,
+};
+use crate::types::Tranche;
+
+pub(crate) const INDEX_SCALE: i128 = 1_000_000_000_000;
+
+pub fn calculate_total_assets(env: &Env) -> i128 {
+    read_contract_balance(env) + read_outstanding_principal(env)
+}
+
+pub fn calculate_tranche_assets(env: &Env, tranche: Tranche) -> i128 {
+    match tranche {
+        Tranche::Senior => calculate_total_assets(env) - read_junior_assets(env),
+        Tranche::Junior => read_junior_assets(env),
+    }
+}
+
+pub fn convert_to_shares(env: &Env, tranche: Tranche, assets: i128) -> i128 {
+    let total_shares = read_total_shares(env, tranche);
+    let total_assets = calculate_tranche_assets(env, tranche);
+
+    if total_shares == 0 || total_assets <= 0 {
+        return assets;
+    }
+
+    assets * total_shares / total_assets
+}
+
+pub fn convert_to_shares_rounding_up(env: &Env, tranche: Tranche, assets: i128) -> i128 {
+    let total_shares = read_total_shares(env, tranche);
+    let total_assets = calculate_tranche_assets(env, tranche);
+
+    if total_shares == 0 || total_assets <= 0 {
+        return assets;
+    }
+
+    (assets * total_shares + total_assets - 1) / total_assets
+}
+
+pub fn convert_to_assets(env: &Env, tranche: Tranche, shares: i128) -> i128 {
+    let total_shares = read_total_shares(env, tranche);
+
+    if total_shares == 0 {
+        return shares;
+    }
+
+    shares * calculate_tranche_assets(env, tranche) / total_shares
+}
+
+fn calculate_pending_loss(env: &Env, tranche: Tranche, lender: &Address) -> i128 {
+    read_shares(env, tranche, lender)
+        * (read_loss_index(env, tranche) - read_loss_checkpoint(env, tranche, lender))
+        / INDEX_SCALE
+}
+
+fn calculate_pending_income(env: &Env, tranche: Tranche, lender: &Address) -> i128 {
+    read_shares(env, tranche, lender)
+        * (read_income_index(env, tranche) - read_income_checkpoint(env, tranche, lender))
+        / INDEX_SCALE
+}
+
+pub fn calculate_lender_loss(env: &Env, lender: &Address) -> i128 {
+    let settled = read_lender(env, lender).map_or(0, |lender| lender.losses);
+    let pending = calculate_pending_loss(env, Tranche::Senior, lender)
+        + calculate_pending_loss(env, Tranche::Junior, lender);
+
+    (settled + pending).max(0)
+}
+
+pub fn calculate_lender_interest(env: &Env, lender: &Address) -> i128 {
+    let settled = read_lender(env, lender).map_or(0, |lender| lender.interest_earned);
+
+    settled
+        + calculate_pending_income(env, Tranche::Senior, lender)
+        + calculate_pending_income(env, Tranche::Junior, lender)
+}
+
+/// Moves the income and losses accrued by the shares of a tranche since the
+/// last checkpoint into the lender record.
+pub fn settle_lender(env: &Env, tranche: Tranche, address: &Address) {
+    if let Ok(mut lender) = read_lender(env, address) {
+        lender.losses = (lender.losses + calculate_pending_loss(env, tranche, address)).max(0);
+        lender.interest_earned += calculate_pending_income(env, tranche, address);
+        write_lender(env, address, &lender);
+    }
+
+    write_loss_checkpoint(env, tranche, address, &read_loss_index(env, tranche));
+    write_income_checkpoint(env, tranche, address, &read_income_index(env, tranche));
+}
+
+/// Spreads a loss (or a recovery, when negative) over the shares of a tranche.
+pub fn distribute_loss(env: &Env, tranche: Tranche, amount: i128) {
+    let total_shares = read_total_shares(env, tranche);
+
+    if total_shares > 0 {
+        let index = read_loss_index(env, tranche) + amount * INDEX_SCALE / total_shares;
+        write_loss_index(env, tranche, &index);
+    }
+}
+
+pub fn distribute_income(env: &Env, tranche: Tranche, amount: i128) {
+    let total_shares = read_total_shares(env, tranche);
+
+    if total_shares > 0 && amount > 0 {
+        let index = read_income_index(env, tranche) + amount * INDEX_SCALE / total_shares;
+        write_income_index(env, tranche, &index);
+    }
+}
+
+fn read_unlocked_shares(env: &Env, tranche: Tranche, address: &Address) -> i128 {
+    match tranche {
+        Tranche::Senior => read_shares(env, tranche, address) - read_locked_shares(env, address),
+        Tranche::Junior => read_shares(env, tranche, address),
+    }
+}
+
+pub fn mint_shares(env: &Env, tranche: Tranche, to: &Address, shares: i128) {
+    settle_lender(env, tranche, to);
+    write_shares(env, tranche, to, &(read_shares(env, tranche, to) + shares));
+    write_total_shares(env, tranche, &(read_total_shares(env, tranche) + shares));
+}
+
+pub fn burn_shares(
+    env: &Env,
+    tranche: Tranche,
+    from: &Address,
+    shares: i128,
+) -> Result<(), LPError> {
+    if shares > read_unlocked_shares(env, tranche, from) {
+        return Err(LPError::InsufficientBalance);
+    }
+
+    settle_lender(env, tranche, from);
+    write_shares(
+        env,
+        tranche,
+        from,
+        &(read_shares(env, tranche, from) - shares),
+    );
+    write_total_shares(env, tranche, &(read_total_shares(env, tranche) - shares));
+    Ok(())
+}
+
+pub fn transfer_shares(
+    env: &Env,
+    tranche: Tranche,
+    from: &Address,
+    to: &Address,
+    shares: i128,
+) -> Result<(), LPError> {
+    if shares > read_unlocked_shares(env, tranche, from) {
+        return Err(LPError::InsufficientBalance);
+    }
+
+    settle_lender(env, tranche, from);
+    settle_lender(env, tranche, to);
+    write_shares(
+        env,
+        tranche,
+        from,
+        &(read_shares(env, tranche, from) - shares),
+    );
+    write_shares(env, tranche, to, &(read_shares(env, tranche, to) + shares));
+    Ok(())
+}
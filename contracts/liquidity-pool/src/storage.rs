@@ -1,20 +1,52 @@
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{Address, Env, Map, Symbol, TryFromVal, Val, Vec};
 
 use crate::{
     errors::LPError,
-    types::{DataKey, Lender, Loan},
+    types::{
+        Allowance, Borrower, Collateral, CollateralConfig, DataKey, DepositTerm, InterestRateModel,
+        LegacyLender, LegacyLoan, Lender, Loan, LockedPosition, MaturityTerms, OriginationFee,
+        OriginationFeeMode, PauseStatus, QueuedChange, Role, Tranche, WithdrawalRequest,
+    },
 };
 
+const DEFAULT_PROTOCOL_FEE: u32 = 1000;
+const DEFAULT_TERM_MULTIPLIER: u32 = 10_000;
+const DEFAULT_JUNIOR_PREMIUM: u32 = 2000;
+const DEFAULT_MAX_PRICE_AGE: u64 = 3600;
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
 pub fn check_admin(env: &Env) -> Result<Address, LPError> {
     let admin = read_admin(env)?;
     admin.require_auth();
     Ok(admin)
 }
 
+/// Requires the auth of `caller` and checks that it holds `role`. The admin
+/// holds every role.
+pub fn check_role(env: &Env, caller: &Address, role: Role) -> Result<(), LPError> {
+    caller.require_auth();
+
+    if *caller != read_admin(env)? && !has_role(env, role, caller) {
+        return Err(LPError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+pub fn has_role(env: &Env, role: Role, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Role(role, address.clone()))
+}
+
 pub fn has_admin(env: &Env) -> bool {
     env.storage().persistent().has(&DataKey::Admin)
 }
 
+pub fn read_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::PendingAdmin)
+}
+
 pub fn read_admin(env: &Env) -> Result<Address, LPError> {
     match env.storage().persistent().get(&DataKey::Admin) {
         Some(admin) => Ok(admin),
@@ -22,23 +54,54 @@ pub fn read_admin(env: &Env) -> Result<Address, LPError> {
     }
 }
 
+pub fn read_pause_status(env: &Env) -> PauseStatus {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PauseStatus)
+        .unwrap_or(PauseStatus {
+            deposit: false,
+            loan: false,
+            withdraw: false,
+        })
+}
+
+pub fn read_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(LEGACY_SCHEMA_VERSION)
+}
+
 pub fn has_borrower(env: &Env, borrower: &Address) -> bool {
     env.storage()
         .persistent()
         .has(&DataKey::Borrower(borrower.clone()))
 }
 
+pub fn has_collateral_token(env: &Env, token: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::CollateralToken(token.clone()))
+}
+
 pub fn has_lender(env: &Env, lender: &Address) -> bool {
     env.storage()
         .persistent()
         .has(&DataKey::Lender(lender.clone()))
 }
 
-pub fn read_borrower(env: &Env, borrower: &Address) -> Result<bool, LPError> {
+pub fn read_borrower(env: &Env, borrower: &Address) -> Result<Borrower, LPError> {
     env.storage()
         .persistent()
         .get(&DataKey::Borrower(borrower.clone()))
-        .ok_or(LPError::BorrowerNotFound)
+        .ok_or(LPError::BorrowerNotRegistered)
+}
+
+pub fn read_collateral_token(env: &Env, token: &Address) -> Result<CollateralConfig, LPError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CollateralToken(token.clone()))
+        .ok_or(LPError::CollateralTokenNotAllowed)
 }
 
 pub fn read_contract_balance(env: &Env) -> i128 {
@@ -48,13 +111,65 @@ pub fn read_contract_balance(env: &Env) -> i128 {
         .unwrap_or(0)
 }
 
-pub fn read_contributions(env: &Env) -> Vec<Address> {
+pub fn read_interest_rate_model(env: &Env) -> Result<InterestRateModel, LPError> {
+    match env.storage().persistent().get(&DataKey::InterestRateModel) {
+        Some(model) => Ok(model),
+        None => Err(LPError::InterestRateNotFound),
+    }
+}
+
+pub fn read_legacy_borrower(env: &Env, borrower: &Address) -> Option<bool> {
+    let record: Val = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Borrower(borrower.clone()))?;
+
+    bool::try_from_val(env, &record).ok()
+}
+
+pub fn read_legacy_lender(env: &Env, lender: &Address) -> Option<LegacyLender> {
+    let record: Val = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Lender(lender.clone()))?;
+    let fields = Map::<Symbol, Val>::try_from_val(env, &record).ok()?;
+
+    if !fields.contains_key(Symbol::new(env, "balance")) {
+        return None;
+    }
+
+    LegacyLender::try_from_val(env, &record).ok()
+}
+
+/// Lenders with a balance, kept by schema version 1 under a key that no
+/// longer exists.
+pub fn read_legacy_contributions(env: &Env) -> Vec<Address> {
     env.storage()
         .persistent()
-        .get(&DataKey::Contribution)
+        .get(&(Symbol::new(env, "Contribution"),))
         .unwrap_or(Vec::new(env))
 }
 
+pub fn read_legacy_loans(env: &Env, borrower: &Address) -> Option<Vec<LegacyLoan>> {
+    let records: Vec<Val> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Loan(borrower.clone()))?;
+    let mut loans = Vec::new(env);
+
+    for record in records.iter() {
+        let fields = Map::<Symbol, Val>::try_from_val(env, &record).ok()?;
+
+        if !fields.contains_key(Symbol::new(env, "contributions")) {
+            return None;
+        }
+
+        loans.push_back(LegacyLoan::try_from_val(env, &record).ok()?);
+    }
+
+    Some(loans)
+}
+
 pub fn read_loans(env: &Env, borrower: &Address) -> Vec<Loan> {
     env.storage()
         .persistent()
@@ -62,11 +177,277 @@ pub fn read_loans(env: &Env, borrower: &Address) -> Vec<Loan> {
         .unwrap_or(Vec::new(env))
 }
 
+pub fn read_maturity_terms(env: &Env) -> MaturityTerms {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MaturityTerms)
+        .unwrap_or(MaturityTerms {
+            max_tenor: None,
+            grace_period: 0,
+            penalty_rate: 0,
+        })
+}
+
+pub fn read_max_open_loans(env: &Env) -> Option<u32> {
+    env.storage().persistent().get(&DataKey::MaxOpenLoans)
+}
+
+pub fn read_max_price_age(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MaxPriceAge)
+        .unwrap_or(DEFAULT_MAX_PRICE_AGE)
+}
+
+pub fn read_oracle(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Oracle)
+}
+
+pub fn read_outstanding_principal(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OutstandingPrincipal)
+        .unwrap_or(0)
+}
+
+pub fn read_loss_index(env: &Env, tranche: Tranche) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LossIndex(tranche))
+        .unwrap_or(0)
+}
+
+pub fn read_loss_checkpoint(env: &Env, tranche: Tranche, lender: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LossCheckpoint(tranche, lender.clone()))
+        .unwrap_or(0)
+}
+
+pub fn read_income_index(env: &Env, tranche: Tranche) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IncomeIndex(tranche))
+        .unwrap_or(0)
+}
+
+pub fn read_income_checkpoint(env: &Env, tranche: Tranche, lender: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IncomeCheckpoint(tranche, lender.clone()))
+        .unwrap_or(0)
+}
+
+pub fn read_junior_assets(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::JuniorAssets)
+        .unwrap_or(0)
+}
+
+pub fn read_senior_loss(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SeniorLoss)
+        .unwrap_or(0)
+}
+
+pub fn read_max_senior_ratio(env: &Env) -> Option<u32> {
+    env.storage().persistent().get(&DataKey::MaxSeniorRatio)
+}
+
+pub fn read_junior_premium(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::JuniorPremium)
+        .unwrap_or(DEFAULT_JUNIOR_PREMIUM)
+}
+
+pub fn read_boost_index(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BoostIndex)
+        .unwrap_or(0)
+}
+
+pub fn read_boost_weight(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BoostWeight)
+        .unwrap_or(0)
+}
+
+pub fn read_boost_balance(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BoostBalance)
+        .unwrap_or(0)
+}
+
+pub fn read_term_multiplier(env: &Env, term: DepositTerm) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TermMultiplier(term))
+        .unwrap_or(DEFAULT_TERM_MULTIPLIER)
+}
+
+pub fn read_locked_shares(env: &Env, lender: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LockedShares(lender.clone()))
+        .unwrap_or(0)
+}
+
+pub fn read_positions(env: &Env, lender: &Address) -> Vec<LockedPosition> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Positions(lender.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn read_queued_shares(env: &Env, tranche: Tranche) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::QueuedShares(tranche))
+        .unwrap_or(0)
+}
+
+pub fn read_withdrawal_queue_head(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WithdrawalQueueHead)
+        .unwrap_or(0)
+}
+
+pub fn read_min_delay(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MinDelay)
+        .unwrap_or(0)
+}
+
+pub fn read_next_change_id(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::NextChangeId)
+        .unwrap_or(0)
+}
+
+pub fn read_change_queue_head(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ChangeQueueHead)
+        .unwrap_or(0)
+}
+
+pub fn read_queued_change(env: &Env, id: u64) -> Option<QueuedChange> {
+    env.storage().persistent().get(&DataKey::QueuedChange(id))
+}
+
+pub fn read_withdrawal_queue_tail(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WithdrawalQueueTail)
+        .unwrap_or(0)
+}
+
+pub fn read_withdrawal_request(env: &Env, id: u64) -> Option<WithdrawalRequest> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WithdrawalRequest(id))
+}
+
+pub fn read_lender_withdrawal(
+    env: &Env,
+    tranche: Tranche,
+    lender: &Address,
+) -> Result<u64, LPError> {
+    match env
+        .storage()
+        .persistent()
+        .get(&DataKey::LenderWithdrawal(tranche, lender.clone()))
+    {
+        Some(id) => Ok(id),
+        None => Err(LPError::WithdrawalRequestNotFound),
+    }
+}
+
+pub fn read_loan_collateral(env: &Env, borrower: &Address, loan_id: u64) -> Option<Collateral> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LoanCollateral(borrower.clone(), loan_id))
+}
+
+pub fn read_origination_fee(env: &Env) -> OriginationFee {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OriginationFee)
+        .unwrap_or(OriginationFee {
+            rate: 0,
+            mode: OriginationFeeMode::Deducted,
+        })
+}
+
+pub fn read_protocol_fee(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProtocolFee)
+        .unwrap_or(DEFAULT_PROTOCOL_FEE)
+}
+
+pub fn read_treasury(env: &Env) -> Result<Address, LPError> {
+    match env.storage().persistent().get(&DataKey::Treasury) {
+        Some(treasury) => Ok(treasury),
+        None => read_admin(env),
+    }
+}
+
+pub fn read_treasury_balance(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TreasuryBalance)
+        .unwrap_or(0)
+}
+
+pub fn read_shares(env: &Env, tranche: Tranche, address: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Shares(tranche, address.clone()))
+        .unwrap_or(0)
+}
+
+pub fn read_total_shares(env: &Env, tranche: Tranche) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TotalShares(tranche))
+        .unwrap_or(0)
+}
+
+pub fn read_allowance(env: &Env, from: &Address, spender: &Address) -> Allowance {
+    let allowance = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Allowance(from.clone(), spender.clone()))
+        .unwrap_or(Allowance {
+            amount: 0,
+            expiration_ledger: 0,
+        });
+
+    if allowance.expiration_ledger < env.ledger().sequence() {
+        return Allowance {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        };
+    }
+
+    allowance
+}
+
 pub fn read_lender(env: &Env, lender: &Address) -> Result<Lender, LPError> {
     env.storage()
         .persistent()
         .get(&DataKey::Lender(lender.clone()))
-        .ok_or(LPError::LenderNotFound)
+        .ok_or(LPError::LenderNotRegistered)
 }
 
 pub fn read_token(env: &Env) -> Result<Address, LPError> {
@@ -76,40 +457,102 @@ pub fn read_token(env: &Env) -> Result<Address, LPError> {
     }
 }
 
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().persistent().remove(&DataKey::PendingAdmin)
+}
+
+pub fn remove_role(env: &Env, role: Role, address: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Role(role, address.clone()))
+}
+
 pub fn remove_borrower(env: &Env, borrower: &Address) {
     env.storage()
         .persistent()
         .remove(&DataKey::Borrower(borrower.clone()))
 }
 
-pub fn remove_lender(env: &Env, lender: &Address) {
+pub fn remove_collateral_token(env: &Env, token: &Address) {
     env.storage()
         .persistent()
-        .remove(&DataKey::Lender(lender.clone()))
+        .remove(&DataKey::CollateralToken(token.clone()))
 }
 
-pub fn remove_lender_contribution(env: &Env, lender: &Address) -> Result<(), LPError> {
-    let mut contributions = read_contributions(env);
+pub fn remove_loan_collateral(env: &Env, borrower: &Address, loan_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::LoanCollateral(borrower.clone(), loan_id))
+}
 
-    if let Some(index) = contributions.iter().position(|address| &address == lender) {
-        match index.try_into() {
-            Ok(valid_index) => contributions.remove(valid_index),
-            Err(_) => return Err(LPError::LenderNotFoundInContributions),
-        };
-    }
+pub fn remove_queued_change(env: &Env, id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::QueuedChange(id));
+}
 
-    write_lender_contribution(env, contributions);
-    Ok(())
+pub fn remove_withdrawal_request(env: &Env, request: &WithdrawalRequest) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::WithdrawalRequest(request.id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::LenderWithdrawal(
+            request.tranche,
+            request.lender.clone(),
+        ));
+}
+
+pub fn remove_legacy_contributions(env: &Env) {
+    env.storage()
+        .persistent()
+        .remove(&(Symbol::new(env, "Contribution"),));
+}
+
+pub fn remove_lender(env: &Env, lender: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Lender(lender.clone()))
 }
 
 pub fn write_admin(env: &Env, admin: &Address) {
     env.storage().persistent().set(&DataKey::Admin, admin);
 }
 
-pub fn write_borrower(env: &Env, borrower: &Address, is_loaned: bool) {
+pub fn write_pending_admin(env: &Env, admin: &Address) {
     env.storage()
         .persistent()
-        .set(&DataKey::Borrower(borrower.clone()), &is_loaned);
+        .set(&DataKey::PendingAdmin, admin);
+}
+
+pub fn write_schema_version(env: &Env, version: &u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SchemaVersion, version);
+}
+
+pub fn write_pause_status(env: &Env, status: &PauseStatus) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PauseStatus, status);
+}
+
+pub fn write_role(env: &Env, role: Role, address: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Role(role, address.clone()), &true);
+}
+
+pub fn write_borrower(env: &Env, borrower: &Address, data: &Borrower) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Borrower(borrower.clone()), data);
+}
+
+pub fn write_collateral_token(env: &Env, token: &Address, config: &CollateralConfig) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::CollateralToken(token.clone()), config);
 }
 
 pub fn write_contract_balance(env: &Env, amount: &i128) {
@@ -118,22 +561,230 @@ pub fn write_contract_balance(env: &Env, amount: &i128) {
         .set(&DataKey::TotalBalance, amount);
 }
 
+pub fn write_interest_rate_model(env: &Env, model: &InterestRateModel) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::InterestRateModel, model);
+}
+
 pub fn write_loans(env: &Env, borrower: &Address, loans: &Vec<Loan>) {
     env.storage()
         .persistent()
         .set(&DataKey::Loan(borrower.clone()), loans);
 }
 
+pub fn write_loan_collateral(env: &Env, borrower: &Address, loan_id: u64, collateral: &Collateral) {
+    env.storage().persistent().set(
+        &DataKey::LoanCollateral(borrower.clone(), loan_id),
+        collateral,
+    );
+}
+
+pub fn write_shares(env: &Env, tranche: Tranche, address: &Address, amount: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Shares(tranche, address.clone()), amount);
+}
+
+pub fn write_total_shares(env: &Env, tranche: Tranche, amount: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TotalShares(tranche), amount);
+}
+
+pub fn write_allowance(env: &Env, from: &Address, spender: &Address, allowance: &Allowance) {
+    env.storage().persistent().set(
+        &DataKey::Allowance(from.clone(), spender.clone()),
+        allowance,
+    );
+}
+
 pub fn write_lender(env: &Env, lender: &Address, data: &Lender) {
     env.storage()
         .persistent()
         .set(&DataKey::Lender(lender.clone()), data);
 }
 
-pub fn write_lender_contribution(env: &Env, contributions: Vec<Address>) {
+pub fn write_maturity_terms(env: &Env, terms: &MaturityTerms) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MaturityTerms, terms);
+}
+
+pub fn write_max_open_loans(env: &Env, max_open_loans: &Option<u32>) {
+    match max_open_loans {
+        Some(max_open_loans) => env
+            .storage()
+            .persistent()
+            .set(&DataKey::MaxOpenLoans, max_open_loans),
+        None => env.storage().persistent().remove(&DataKey::MaxOpenLoans),
+    }
+}
+
+pub fn write_max_price_age(env: &Env, max_age: &u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MaxPriceAge, max_age);
+}
+
+pub fn write_oracle(env: &Env, oracle: &Address) {
+    env.storage().persistent().set(&DataKey::Oracle, oracle);
+}
+
+pub fn write_outstanding_principal(env: &Env, amount: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OutstandingPrincipal, amount);
+}
+
+pub fn write_loss_index(env: &Env, tranche: Tranche, index: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::LossIndex(tranche), index);
+}
+
+pub fn write_loss_checkpoint(env: &Env, tranche: Tranche, lender: &Address, index: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::LossCheckpoint(tranche, lender.clone()), index);
+}
+
+pub fn write_income_index(env: &Env, tranche: Tranche, index: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::IncomeIndex(tranche), index);
+}
+
+pub fn write_income_checkpoint(env: &Env, tranche: Tranche, lender: &Address, index: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::IncomeCheckpoint(tranche, lender.clone()), index);
+}
+
+pub fn write_junior_assets(env: &Env, amount: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::JuniorAssets, amount);
+}
+
+pub fn write_senior_loss(env: &Env, amount: &i128) {
+    env.storage().persistent().set(&DataKey::SeniorLoss, amount);
+}
+
+pub fn write_max_senior_ratio(env: &Env, ratio: &u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MaxSeniorRatio, ratio);
+}
+
+pub fn write_junior_premium(env: &Env, premium: &u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::JuniorPremium, premium);
+}
+
+pub fn write_boost_index(env: &Env, index: &i128) {
+    env.storage().persistent().set(&DataKey::BoostIndex, index);
+}
+
+pub fn write_boost_weight(env: &Env, weight: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BoostWeight, weight);
+}
+
+pub fn write_boost_balance(env: &Env, amount: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BoostBalance, amount);
+}
+
+pub fn write_term_multiplier(env: &Env, term: DepositTerm, multiplier: &u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TermMultiplier(term), multiplier);
+}
+
+pub fn write_locked_shares(env: &Env, lender: &Address, shares: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockedShares(lender.clone()), shares);
+}
+
+pub fn write_positions(env: &Env, lender: &Address, positions: &Vec<LockedPosition>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Positions(lender.clone()), positions);
+}
+
+pub fn write_queued_shares(env: &Env, tranche: Tranche, shares: &i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::QueuedShares(tranche), shares);
+}
+
+pub fn write_withdrawal_queue_head(env: &Env, id: &u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WithdrawalQueueHead, id);
+}
+
+pub fn write_min_delay(env: &Env, delay: &u64) {
+    env.storage().persistent().set(&DataKey::MinDelay, delay);
+}
+
+pub fn write_next_change_id(env: &Env, id: &u64) {
+    env.storage().persistent().set(&DataKey::NextChangeId, id);
+}
+
+pub fn write_change_queue_head(env: &Env, id: &u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ChangeQueueHead, id);
+}
+
+pub fn write_queued_change(env: &Env, queued: &QueuedChange) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::QueuedChange(queued.id), queued);
+}
+
+pub fn write_withdrawal_queue_tail(env: &Env, id: &u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WithdrawalQueueTail, id);
+}
+
+pub fn write_withdrawal_request(env: &Env, request: &WithdrawalRequest) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WithdrawalRequest(request.id), request);
+    env.storage().persistent().set(
+        &DataKey::LenderWithdrawal(request.tranche, request.lender.clone()),
+        &request.id,
+    );
+}
+
+pub fn write_origination_fee(env: &Env, origination_fee: &OriginationFee) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OriginationFee, origination_fee);
+}
+
+pub fn write_protocol_fee(env: &Env, protocol_fee: &u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProtocolFee, protocol_fee);
+}
+
+pub fn write_treasury(env: &Env, treasury: &Address) {
+    env.storage().persistent().set(&DataKey::Treasury, treasury);
+}
+
+pub fn write_treasury_balance(env: &Env, amount: &i128) {
     env.storage()
         .persistent()
-        .set(&DataKey::Contribution, &contributions);
+        .set(&DataKey::TreasuryBalance, amount);
 }
 
 pub fn write_token(env: &Env, address: &Address) {
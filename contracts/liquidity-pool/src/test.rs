@@ -1,10 +1,19 @@
 #![cfg(test)]
 extern crate std;
 
-use super::testutils::{create_token_contract, set_timestamp_for_20_days, Setup};
+use super::testutils::{
+    create_oracle_contract, create_token_contract, set_oracle_price, set_timestamp_for_20_days,
+    Setup, COLLATERAL_CONFIG, INTEREST_RATE, LOAN_DURATION, ORACLE_PRICE,
+};
+use crate::types::{
+    Borrower, BorrowerTerms, Collateral, CollateralConfig, DepositTerm, Installment,
+    InterestRateModel, LegacyLoan, LenderSummary, LoanSchedule, LoanStatus, LockedPosition,
+    MaturityTerms, Operation, OriginationFee, OriginationFeeMode, ParameterChange, PauseStatus,
+    QueuedChange, RepaymentSchedule, RepaymentSplit, Role, Tranche,
+};
 use soroban_sdk::{
-    testutils::{Address as _, MockAuth, MockAuthInvoke},
-    vec, Address, Env, IntoVal, Symbol,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    vec, Address, BytesN, Env, IntoVal, Map, String, Symbol, Vec,
 };
 
 #[test]
@@ -30,7 +39,7 @@ fn test_initialize() {
                     setup.admin.into_val(&setup.env),
                     setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             )
         ]
     );
@@ -49,253 +58,406 @@ fn test_already_initialize() {
     setup
         .liquid_contract
         .client()
-        .initialize(&admin, &token.address);
+        .initialize(&admin, &token.address, &INTEREST_RATE);
 }
 
 #[test]
-fn test_balance_with_admin() {
+fn test_total_assets() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
 
-    let balance = setup.liquid_contract.client().balance(&setup.admin);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &400i128, &LOAN_DURATION, &None, &None);
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), balance);
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 600i128);
+    assert_eq!(setup.liquid_contract.client().total_assets(), 1000i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .total_shares(&Tranche::Senior),
+        1000i128
+    );
 }
 
 #[test]
 fn test_balance_with_lender() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &10i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &10i128, &Tranche::Senior);
 
     let balance = setup.liquid_contract.client().balance(&lender);
 
+    assert_eq!(balance, 10i128);
     assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(balance));
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
-fn test_balance_without_registered_address() {
+fn test_balance_without_shares() {
     let setup = Setup::new();
 
     let unregistered_address = Address::generate(&setup.env);
 
-    setup
-        .liquid_contract
-        .client()
-        .balance(&unregistered_address);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .balance(&unregistered_address),
+        0i128
+    );
 }
 
 #[test]
-fn test_deposit() {
+fn test_shares_value_grows_with_interest() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
     let lender1 = Address::generate(&setup.env);
     let lender2 = Address::generate(&setup.env);
 
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+    setup.liquid_contract.execute_change(id);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender1);
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup.token_admin.mint(&lender1, &1000i128);
+    setup.token_admin.mint(&lender2, &1180i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
 
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &200i128);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender2);
+        .repay_in_full(&borrower, &loan_id);
 
-    setup.token_admin.mock_all_auths().mint(&lender1, &4i128);
-    setup.token_admin.mock_all_auths().mint(&lender2, &7i128);
+    assert_eq!(setup.liquid_contract.client().total_assets(), 1180i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .convert_to_assets(&1000i128, &Tranche::Senior),
+        1180i128
+    );
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender1, &4i128);
+        .deposit(&lender2, &1180i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.client().balance(&lender2), 1000i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .total_shares(&Tranche::Senior),
+        2000i128
+    );
+
+    let amount = setup
+        .liquid_contract
+        .client()
+        .redeem(&lender1, &1000i128, &Tranche::Senior);
+
+    assert_eq!(amount, 1180i128);
+    assert_eq!(setup.token.balance(&lender1), 1180i128);
+    assert_eq!(setup.liquid_contract.client().balance(&lender1), 0i128);
+    assert!(!setup.liquid_contract.has_shares(&lender1));
+}
+
+#[test]
+fn test_share_token_metadata() {
+    let setup = Setup::new();
+
+    assert_eq!(
+        setup.liquid_contract.client().decimals(),
+        setup.token.decimals()
+    );
+    assert_eq!(
+        setup.liquid_contract.client().name(),
+        String::from_str(&setup.env, "Clear Liquidity Pool Share")
+    );
+    assert_eq!(
+        setup.liquid_contract.client().symbol(),
+        String::from_str(&setup.env, "CLPS")
+    );
+}
+
+#[test]
+fn test_transfer_shares() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+    let receiver = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender2, &7i128);
+        .add_lender(&setup.admin, &lender);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &receiver);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
 
-    let contract_events = setup.liquid_contract.get_contract_events();
+    setup
+        .liquid_contract
+        .client()
+        .transfer(&lender, &receiver, &400i128);
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 11i128);
-    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(4i128));
-    assert!(setup.liquid_contract.is_lender_in_contributions(&lender1));
+    let contract_events = setup.liquid_contract.get_contract_events();
 
-    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(7i128));
-    assert!(setup.liquid_contract.is_lender_in_contributions(&lender2));
+    assert_eq!(setup.liquid_contract.client().balance(&lender), 600i128);
+    assert_eq!(setup.liquid_contract.client().balance(&receiver), 400i128);
     assert_eq!(
-        contract_events,
+        vec![&setup.env, contract_events.last().unwrap()],
         vec![
             &setup.env,
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "initialize").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    setup.token.address.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_lender").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    lender1.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_lender").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    lender2.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "deposit").as_val(),
-                    lender1.into_val(&setup.env),
-                ],
-                4i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "deposit").as_val(),
-                    lender2.into_val(&setup.env),
+                    *Symbol::new(&setup.env, "transfer").as_val(),
+                    lender.into_val(&setup.env),
+                    receiver.into_val(&setup.env),
                 ],
-                7i128.into_val(&setup.env)
+                400i128.into_val(&setup.env)
             )
         ]
     );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #10)")]
-fn test_deposit_without_lender() {
+fn test_transfer_shares_from_with_allowance() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let lender = Address::generate(&setup.env);
+    let spender = Address::generate(&setup.env);
+    let receiver = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender, &10i128);
+        .add_lender(&setup.admin, &lender);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &receiver);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .approve(&lender, &spender, &300i128, &100u32);
+    setup
+        .liquid_contract
+        .client()
+        .transfer_from(&spender, &lender, &receiver, &200i128);
+
+    assert_eq!(
+        setup.liquid_contract.client().allowance(&lender, &spender),
+        100i128
+    );
+    assert_eq!(setup.liquid_contract.client().balance(&lender), 800i128);
+    assert_eq!(setup.liquid_contract.client().balance(&receiver), 200i128);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_deposit_with_negative_amount() {
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_transfer_shares_to_non_lender() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let lender = Address::generate(&setup.env);
+    let receiver = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender, &-10i128);
+        .transfer(&lender, &receiver, &400i128);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #16)")]
-fn test_deposit_with_disabled_lender() {
+#[should_panic(expected = "Error(Contract, #50)")]
+fn test_deposit_into_wiped_out_tranche() {
     let setup = Setup::new();
     setup.env.mock_all_auths();
-    let lender = Address::generate(&setup.env);
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup.token_admin.mint(&lender1, &1000i128);
+    setup.token_admin.mint(&lender2, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
 
-    setup.liquid_contract.client().add_lender(&lender);
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
 
     setup
         .liquid_contract
         .client()
-        .set_lender_status(&lender, &false);
+        .mark_default(&setup.admin, &borrower, &loan_id);
 
-    setup.liquid_contract.client().deposit(&lender, &10i128);
+    assert_eq!(setup.liquid_contract.client().total_assets(), 0i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &1000i128, &Tranche::Senior);
 }
 
 #[test]
-fn test_withdraw() {
+#[should_panic(expected = "Error(Contract, #42)")]
+fn test_transfer_shares_from_exceeds_allowance() {
     let setup = Setup::new();
-    let lender1 = Address::generate(&setup.env);
-    let lender2 = Address::generate(&setup.env);
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+    let spender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender1);
-
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender2);
+        .deposit(&lender, &1000i128, &Tranche::Senior);
 
-    setup.token_admin.mock_all_auths().mint(&lender1, &10i128);
-    setup.token_admin.mock_all_auths().mint(&lender2, &10i128);
     setup
-        .token_admin
-        .mock_all_auths()
-        .mint(&setup.liquid_contract_id, &20i128);
+        .liquid_contract
+        .client()
+        .approve(&lender, &spender, &300i128, &100u32);
+    setup
+        .liquid_contract
+        .client()
+        .transfer_from(&spender, &lender, &spender, &400i128);
+}
+
+#[test]
+fn test_deposit() {
+    let setup = Setup::new();
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .deposit(&lender1, &10i128);
+        .add_lender(&setup.admin, &lender1);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .deposit(&lender2, &10i128);
+        .add_lender(&setup.admin, &lender2);
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 20i128);
-    assert!(setup.liquid_contract.is_lender_in_contributions(&lender1));
-    assert!(setup.liquid_contract.is_lender_in_contributions(&lender2));
+    setup.token_admin.mock_all_auths().mint(&lender1, &4i128);
+    setup.token_admin.mock_all_auths().mint(&lender2, &7i128);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .withdraw(&lender1, &5i128);
+        .deposit(&lender1, &4i128, &Tranche::Senior);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .withdraw(&lender2, &7i128);
+        .deposit(&lender2, &7i128, &Tranche::Senior);
 
     let contract_events = setup.liquid_contract.get_contract_events();
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 8i128);
-    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(5i128));
-    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(3i128));
-    assert!(setup.liquid_contract.is_lender_in_contributions(&lender1));
-    assert!(setup.liquid_contract.is_lender_in_contributions(&lender2));
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 11i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(4i128));
+    assert!(setup.liquid_contract.has_shares(&lender1));
+
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(7i128));
+    assert!(setup.liquid_contract.has_shares(&lender2));
     assert_eq!(
         contract_events,
         vec![
@@ -308,7 +470,7 @@ fn test_withdraw() {
                     setup.admin.into_val(&setup.env),
                     setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -336,8 +498,9 @@ fn test_withdraw() {
                     &setup.env,
                     *Symbol::new(&setup.env, "deposit").as_val(),
                     lender1.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
                 ],
-                10i128.into_val(&setup.env)
+                4i128.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -345,24 +508,7 @@ fn test_withdraw() {
                     &setup.env,
                     *Symbol::new(&setup.env, "deposit").as_val(),
                     lender2.into_val(&setup.env),
-                ],
-                10i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "withdraw").as_val(),
-                    lender1.into_val(&setup.env),
-                ],
-                5i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "withdraw").as_val(),
-                    lender2.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
                 ],
                 7i128.into_val(&setup.env)
             )
@@ -371,7 +517,8 @@ fn test_withdraw() {
 }
 
 #[test]
-fn test_withdraw_by_remove_contribution() {
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_deposit_without_lender() {
     let setup = Setup::new();
     let lender = Address::generate(&setup.env);
 
@@ -379,447 +526,623 @@ fn test_withdraw_by_remove_contribution() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_lender(&lender);
-
-    setup.token_admin.mock_all_auths().mint(&lender, &10i128);
-    setup
-        .token_admin
-        .mock_all_auths()
-        .mint(&setup.liquid_contract_id, &10i128);
+        .deposit(&lender, &10i128, &Tranche::Senior);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_deposit_with_negative_amount() {
+    let setup = Setup::new();
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .deposit(&lender, &10i128);
-
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 10i128);
-    assert!(setup.liquid_contract.is_lender_in_contributions(&lender));
+        .add_lender(&setup.admin, &lender);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .withdraw(&lender, &10i128);
-
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
-    assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(0i128));
-    assert!(!setup.liquid_contract.is_lender_in_contributions(&lender));
+        .deposit(&lender, &-10i128, &Tranche::Senior);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #10)")]
-fn test_withdraw_without_lender() {
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_deposit_with_disabled_lender() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .withdraw(&lender, &7i128);
-}
+        .add_lender(&setup.admin, &lender);
 
-#[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_withdraw_negative_amount() {
-    let setup = Setup::new();
-    let lender = Address::generate(&setup.env);
+    setup
+        .liquid_contract
+        .client()
+        .set_lender_status(&setup.admin, &lender, &false);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender);
+        .deposit(&lender, &10i128, &Tranche::Senior);
+}
 
-    setup.token_admin.mock_all_auths().mint(&lender, &7i128);
+#[test]
+fn test_deposit_junior() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender, &7i128);
-
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .withdraw(&lender, &-7i128);
+        .deposit(&lender, &1000i128, &Tranche::Junior);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Junior),
+        1000i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .shares(&lender, &Tranche::Junior),
+        1000i128
+    );
+    assert_eq!(setup.liquid_contract.client().balance(&lender), 0i128);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender.into_val(&setup.env),
+                    Tranche::Junior.into_val(&setup.env),
+                ],
+                1000i128.into_val(&setup.env)
+            )
+        ]
+    );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #7)")]
-fn test_withdraw_amount_greater_lender_balance() {
+fn test_junior_earns_interest_premium() {
     let setup = Setup::new();
-    let lender = Address::generate(&setup.env);
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let senior = Address::generate(&setup.env);
+    let junior = Address::generate(&setup.env);
 
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+    setup.liquid_contract.execute_change(id);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender);
-
-    setup.token_admin.mock_all_auths().mint(&lender, &7i128);
-
+        .add_lender(&setup.admin, &senior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender, &7i128);
+        .add_lender(&setup.admin, &junior);
+    setup.token_admin.mint(&senior, &1000i128);
+    setup.token_admin.mint(&junior, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&senior, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&junior, &1000i128, &Tranche::Junior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
 
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &200i128);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .withdraw(&lender, &10i128);
+        .repay_in_full(&borrower, &loan_id);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Junior),
+        1108i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Senior),
+        1072i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .redeem(&junior, &1000i128, &Tranche::Junior),
+        1108i128
+    );
 }
 
 #[test]
-fn test_loan() {
+fn test_default_absorbed_by_junior_first() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
-    let lender1 = Address::generate(&setup.env);
-    let lender2 = Address::generate(&setup.env);
+    let senior = Address::generate(&setup.env);
+    let junior = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender1);
-
+        .add_lender(&setup.admin, &senior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender2);
-
-    setup.token_admin.mock_all_auths().mint(&lender1, &10i128);
-    setup.token_admin.mock_all_auths().mint(&lender2, &10i128);
+        .add_lender(&setup.admin, &junior);
+    setup.token_admin.mint(&senior, &1000i128);
+    setup.token_admin.mint(&junior, &500i128);
     setup
-        .token_admin
-        .mock_all_auths()
-        .mint(&setup.liquid_contract_id, &20i128);
-
+        .liquid_contract
+        .client()
+        .deposit(&senior, &1000i128, &Tranche::Senior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender1, &10i128);
-
+        .deposit(&junior, &500i128, &Tranche::Junior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender2, &10i128);
+        .add_borrower(&setup.admin, &borrower);
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 20i128);
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1200i128, &LOAN_DURATION, &None, &None);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Junior),
+        0i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Senior),
+        300i128
+    );
+    assert_eq!(setup.liquid_contract.client().lender_loss(&junior), 500i128);
+    assert_eq!(setup.liquid_contract.client().lender_loss(&senior), 700i128);
 
-    let loan_id = setup
+    setup.token_admin.mint(&setup.admin, &1000i128);
+    setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .loan(&borrower, &10i128);
-
-    let contract_events = setup.liquid_contract.get_contract_events();
+        .recover(&setup.admin, &borrower, &loan_id, &1000i128);
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 10i128);
-    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
-    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(5i128));
-    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(5i128));
     assert_eq!(
-        contract_events,
-        vec![
-            &setup.env,
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "initialize").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    setup.token.address.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_lender").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    lender1.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_lender").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    lender2.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "deposit").as_val(),
-                    lender1.into_val(&setup.env),
-                ],
-                10i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "deposit").as_val(),
-                    lender2.into_val(&setup.env),
-                ],
-                10i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_borrower").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    borrower.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "loan").as_val(),
-                    borrower.into_val(&setup.env),
-                    loan_id.into_val(&setup.env),
-                ],
-                10i128.into_val(&setup.env)
-            )
-        ]
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Junior),
+        300i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Senior),
+        1000i128
     );
+    assert_eq!(setup.liquid_contract.client().lender_loss(&junior), 200i128);
+    assert_eq!(setup.liquid_contract.client().lender_loss(&senior), 0i128);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_loan_negative_amount() {
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_deposit_exceeds_max_senior_ratio() {
     let setup = Setup::new();
-    let borrower = Address::generate(&setup.env);
+    setup.env.mock_all_auths();
+    let senior = Address::generate(&setup.env);
+    let junior = Address::generate(&setup.env);
 
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_senior_ratio(&setup.admin, &20000u32);
+    setup.liquid_contract.execute_change(id);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
-
+        .add_lender(&setup.admin, &senior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .loan(&borrower, &-10i128);
-}
+        .add_lender(&setup.admin, &junior);
+    setup.token_admin.mint(&senior, &1001i128);
+    setup.token_admin.mint(&junior, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&junior, &500i128, &Tranche::Junior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&senior, &1000i128, &Tranche::Senior);
 
-#[test]
-#[should_panic(expected = "Error(Contract, #6)")]
-fn test_loan_without_borrower() {
-    let setup = Setup::new();
-    let borrower = Address::generate(&setup.env);
+    assert_eq!(
+        setup.liquid_contract.client().max_senior_ratio(),
+        Some(20000u32)
+    );
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .loan(&borrower, &10i128);
+        .deposit(&senior, &1i128, &Tranche::Senior);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #18)")]
-fn test_loan_with_disable_borrower() {
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_junior_withdraw_exceeds_max_senior_ratio() {
     let setup = Setup::new();
     setup.env.mock_all_auths();
-    let borrower = Address::generate(&setup.env);
-
-    setup.liquid_contract.client().add_borrower(&borrower);
+    let senior = Address::generate(&setup.env);
+    let junior = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .set_borrower_status(&borrower, &false);
-
-    setup.liquid_contract.client().loan(&borrower, &10i128);
-}
-
-#[test]
-fn test_request_two_loans() {
-    let setup = Setup::new();
-    let borrower = Address::generate(&setup.env);
-    let lender = Address::generate(&setup.env);
-
+        .add_lender(&setup.admin, &senior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_lender(&lender);
-
-    setup.token_admin.mock_all_auths().mint(&lender, &20i128);
-
+        .add_lender(&setup.admin, &junior);
+    setup.token_admin.mint(&senior, &1000i128);
+    setup.token_admin.mint(&junior, &500i128);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender, &20i128);
-
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 20i128);
-
+        .deposit(&junior, &500i128, &Tranche::Junior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
+        .deposit(&senior, &1000i128, &Tranche::Senior);
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_senior_ratio(&setup.admin, &20000u32);
+    setup.liquid_contract.execute_change(id);
 
-    let first_loan_id = setup
+    setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .loan(&borrower, &10i128);
+        .withdraw(&junior, &100i128, &Tranche::Junior);
+}
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 10i128);
-    assert!(setup.liquid_contract.has_loan(&borrower, first_loan_id));
+#[test]
+#[should_panic(expected = "Error(Contract, #50)")]
+fn test_set_invalid_junior_premium() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
 
-    let second_loan_id = setup
+    setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .loan(&borrower, &10i128);
-
-    let contract_events = setup.liquid_contract.get_contract_events();
+        .set_junior_premium(&setup.admin, &10001u32);
+}
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
-    assert!(setup.liquid_contract.has_loan(&borrower, second_loan_id));
+#[test]
+fn test_deposit_locked() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().set_term_multiplier(
+        &setup.admin,
+        &DepositTerm::Days30,
+        &15000u32,
+    );
+    setup.liquid_contract.execute_change(id);
+    let start_time = setup.env.ledger().timestamp();
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+
+    let position_id =
+        setup
+            .liquid_contract
+            .client()
+            .deposit_locked(&lender, &1000i128, &DepositTerm::Days30);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+    let positions = setup.liquid_contract.client().positions(&lender);
+
+    assert_eq!(setup.liquid_contract.client().balance(&lender), 1000i128);
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
     assert_eq!(
-        contract_events,
+        positions,
+        vec![
+            &setup.env,
+            LockedPosition {
+                id: position_id,
+                term: DepositTerm::Days30,
+                amount: 1000,
+                shares: 1000,
+                multiplier: 15000,
+                start_time,
+                maturity_time: start_time + 30 * 86400,
+                boost_index: 0,
+                boost: 0,
+            }
+        ]
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
         vec![
             &setup.env,
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "initialize").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    setup.token.address.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_lender").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    lender.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    *Symbol::new(&setup.env, "deposit_locked").as_val(),
                     lender.into_val(&setup.env),
+                    position_id.into_val(&setup.env),
                 ],
-                20i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_borrower").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    borrower.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "loan").as_val(),
-                    borrower.into_val(&setup.env),
-                    first_loan_id.into_val(&setup.env),
-                ],
-                10i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "loan").as_val(),
-                    borrower.into_val(&setup.env),
-                    second_loan_id.into_val(&setup.env),
-                ],
-                10i128.into_val(&setup.env)
+                (1000i128, DepositTerm::Days30).into_val(&setup.env)
             )
         ]
     );
 }
 
 #[test]
-fn test_repay_loan_with_repayment_total_amount() {
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_withdraw_locked_deposit() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit_locked(&lender, &1000i128, &DepositTerm::Days90);
+
+    setup
+        .liquid_contract
+        .client()
+        .withdraw(&lender, &600i128, &Tranche::Senior);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_withdraw_matured_before_maturity() {
     let setup = Setup::new();
     setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+
+    let position_id =
+        setup
+            .liquid_contract
+            .client()
+            .deposit_locked(&lender, &1000i128, &DepositTerm::Days30);
+
+    set_timestamp_for_20_days(&setup.env);
+    setup
+        .liquid_contract
+        .client()
+        .withdraw_matured(&lender, &position_id);
+}
 
+#[test]
+fn test_withdraw_matured_with_boost() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
     let lender1 = Address::generate(&setup.env);
     let lender2 = Address::generate(&setup.env);
 
-    setup.liquid_contract.client().add_lender(&lender1);
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+    setup.liquid_contract.execute_change(id);
+    let id = setup.liquid_contract.client().set_term_multiplier(
+        &setup.admin,
+        &DepositTerm::Days90,
+        &20000u32,
+    );
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup.token_admin.mint(&lender1, &1000i128);
+    setup.token_admin.mint(&lender2, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &1000i128, &Tranche::Senior);
 
-    setup.liquid_contract.client().add_lender(&lender2);
+    let position_id =
+        setup
+            .liquid_contract
+            .client()
+            .deposit_locked(&lender2, &1000i128, &DepositTerm::Days90);
 
-    setup.token_admin.mint(&lender1, &5000i128);
-    setup.token_admin.mint(&lender2, &5000i128);
     setup
-        .token_admin
-        .mint(&setup.liquid_contract_id, &10000i128);
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
 
-    setup.liquid_contract.client().deposit(&lender1, &5000i128);
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &200i128);
+    setup
+        .liquid_contract
+        .client()
+        .repay_in_full(&borrower, &loan_id);
 
-    setup.liquid_contract.client().deposit(&lender2, &5000i128);
+    let position = setup
+        .liquid_contract
+        .client()
+        .positions(&lender2)
+        .get_unchecked(0);
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 10000i128);
+    assert_eq!(position.boost, 60i128);
+    assert_eq!(setup.liquid_contract.client().total_assets(), 2120i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(1060i128));
 
-    setup.liquid_contract.client().add_borrower(&borrower);
+    setup.env.ledger().set_timestamp(position.maturity_time);
 
-    let loan_id = setup.liquid_contract.client().loan(&borrower, &10000i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdraw_matured(&lender2, &position_id),
+        1120i128
+    );
+    assert_eq!(setup.token.balance(&lender2), 1120i128);
+    assert_eq!(setup.liquid_contract.client().balance(&lender2), 0i128);
+    assert_eq!(setup.liquid_contract.client().positions(&lender2).len(), 0);
+}
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
-    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
-    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(0i128));
-    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(0i128));
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn test_set_invalid_term_multiplier() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
 
-    set_timestamp_for_20_days(&setup.env);
+    setup.liquid_contract.client().set_term_multiplier(
+        &setup.admin,
+        &DepositTerm::Days180,
+        &9000u32,
+    );
+}
 
-    setup.token_admin.mint(&borrower, &10020i128);
+#[test]
+fn test_withdraw() {
+    let setup = Setup::new();
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .repay_loan(&borrower, &loan_id, &10020i128);
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender1);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender2);
+
+    setup.token_admin.mock_all_auths().mint(&lender1, &10i128);
+    setup.token_admin.mock_all_auths().mint(&lender2, &10i128);
+    setup
+        .token_admin
+        .mock_all_auths()
+        .mint(&setup.liquid_contract_id, &20i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender1, &10i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender2, &10i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 20i128);
+    assert!(setup.liquid_contract.has_shares(&lender1));
+    assert!(setup.liquid_contract.has_shares(&lender2));
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .withdraw(&lender1, &5i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .withdraw(&lender2, &7i128, &Tranche::Senior);
 
     let contract_events = setup.liquid_contract.get_contract_events();
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 10020i128);
-    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(5009i128));
-    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(5009i128));
-    assert!(!setup.liquid_contract.has_loan(&borrower, loan_id));
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 8i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(5i128));
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(3i128));
+    assert!(setup.liquid_contract.has_shares(&lender1));
+    assert!(setup.liquid_contract.has_shares(&lender2));
     assert_eq!(
         contract_events,
         vec![
@@ -832,7 +1155,7 @@ fn test_repay_loan_with_repayment_total_amount() {
                     setup.admin.into_val(&setup.env),
                     setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -860,8 +1183,9 @@ fn test_repay_loan_with_repayment_total_amount() {
                     &setup.env,
                     *Symbol::new(&setup.env, "deposit").as_val(),
                     lender1.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
                 ],
-                5000i128.into_val(&setup.env)
+                10i128.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -869,175 +1193,5396 @@ fn test_repay_loan_with_repayment_total_amount() {
                     &setup.env,
                     *Symbol::new(&setup.env, "deposit").as_val(),
                     lender2.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
                 ],
-                5000i128.into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_borrower").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    borrower.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
+                10i128.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "loan").as_val(),
-                    borrower.into_val(&setup.env),
-                    loan_id.into_val(&setup.env),
+                    *Symbol::new(&setup.env, "withdraw").as_val(),
+                    lender1.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
                 ],
-                10000i128.into_val(&setup.env)
+                5i128.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "repay_loan").as_val(),
-                    borrower.into_val(&setup.env),
-                    loan_id.into_val(&setup.env),
+                    *Symbol::new(&setup.env, "withdraw").as_val(),
+                    lender2.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
                 ],
-                10020i128.into_val(&setup.env)
+                7i128.into_val(&setup.env)
             )
         ]
     );
 }
 
 #[test]
-fn test_repay_loan_without_repayment_total_amount() {
+fn test_withdraw_by_remove_contribution() {
     let setup = Setup::new();
-    let borrower = Address::generate(&setup.env);
-    let lender1 = Address::generate(&setup.env);
-    let lender2 = Address::generate(&setup.env);
-
-    setup
-        .liquid_contract
-        .client()
-        .mock_all_auths()
-        .add_lender(&lender1);
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_lender(&lender2);
+        .add_lender(&setup.admin, &lender);
 
-    setup.token_admin.mock_all_auths().mint(&lender1, &500i128);
-    setup.token_admin.mock_all_auths().mint(&lender2, &500i128);
+    setup.token_admin.mock_all_auths().mint(&lender, &10i128);
     setup
         .token_admin
         .mock_all_auths()
-        .mint(&setup.liquid_contract_id, &1000i128);
+        .mint(&setup.liquid_contract_id, &10i128);
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .deposit(&lender1, &500i128);
+        .deposit(&lender, &10i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 10i128);
+    assert!(setup.liquid_contract.has_shares(&lender));
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .deposit(&lender2, &500i128);
+        .withdraw(&lender, &10i128, &Tranche::Senior);
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(0i128));
+    assert!(!setup.liquid_contract.has_shares(&lender));
+}
 
-    setup
-        .liquid_contract
-        .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_withdraw_without_lender() {
+    let setup = Setup::new();
+    let lender = Address::generate(&setup.env);
 
-    let loan_id = setup
+    setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .loan(&borrower, &1000i128);
+        .withdraw(&lender, &7i128, &Tranche::Senior);
+}
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
-    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
-    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(0i128));
-    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(0i128));
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_withdraw_negative_amount() {
+    let setup = Setup::new();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender);
+
+    setup.token_admin.mock_all_auths().mint(&lender, &7i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender, &7i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .withdraw(&lender, &-7i128, &Tranche::Senior);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_withdraw_amount_greater_lender_balance() {
+    let setup = Setup::new();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender);
+
+    setup.token_admin.mock_all_auths().mint(&lender, &7i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender, &7i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .withdraw(&lender, &10i128, &Tranche::Senior);
+}
+
+#[test]
+fn test_request_withdrawal() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &800i128, &LOAN_DURATION, &None, &None);
+
+    let request_id =
+        setup
+            .liquid_contract
+            .client()
+            .request_withdrawal(&lender, &1000i128, &Tranche::Senior);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(request_id, 0u64);
+    assert_eq!(setup.liquid_contract.client().balance(&lender), 0i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&lender, &Tranche::Senior),
+        1000i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdrawal_position(&lender, &Tranche::Senior),
+        0u32
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "request_withdrawal").as_val(),
+                    lender.into_val(&setup.env),
+                    request_id.into_val(&setup.env),
+                ],
+                (Tranche::Senior, 1000i128, 1000i128).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #44)")]
+fn test_request_withdrawal_twice() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&lender, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&lender, &500i128, &Tranche::Senior);
+}
+
+#[test]
+fn test_process_withdrawals_after_repay() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &800i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&lender, &1000i128, &Tranche::Senior);
+
+    assert_eq!(
+        setup.liquid_contract.client().process_withdrawals(&10u32),
+        200i128
+    );
+    assert_eq!(setup.token.balance(&lender), 200i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&lender, &Tranche::Senior),
+        800i128
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &800i128);
+
+    assert_eq!(
+        setup.liquid_contract.client().process_withdrawals(&10u32),
+        800i128
+    );
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.token.balance(&lender), 1000i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&lender, &Tranche::Senior),
+        0i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .total_shares(&Tranche::Senior),
+        0i128
+    );
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "fill_withdrawal").as_val(),
+                    lender.into_val(&setup.env),
+                    0u64.into_val(&setup.env),
+                ],
+                (800i128, 800i128).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_process_withdrawals_in_order() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+    let lender3 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender3);
+    setup.token_admin.mint(&lender1, &500i128);
+    setup.token_admin.mint(&lender2, &500i128);
+    setup.token_admin.mint(&lender3, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender3, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1500i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&lender1, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&lender2, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&lender3, &500i128, &Tranche::Senior);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdrawal_position(&lender3, &Tranche::Senior),
+        2u32
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .cancel_withdrawal(&lender1, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.client().balance(&lender1), 500i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdrawal_position(&lender2, &Tranche::Senior),
+        0u32
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdrawal_position(&lender3, &Tranche::Senior),
+        1u32
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &700i128);
+
+    assert_eq!(
+        setup.liquid_contract.client().process_withdrawals(&1u32),
+        0i128
+    );
+    assert_eq!(
+        setup.liquid_contract.client().process_withdrawals(&10u32),
+        700i128
+    );
+    assert_eq!(setup.token.balance(&lender2), 500i128);
+    assert_eq!(setup.token.balance(&lender3), 200i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&lender3, &Tranche::Senior),
+        300i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdrawal_position(&lender3, &Tranche::Senior),
+        0u32
+    );
+}
+
+#[test]
+fn test_process_junior_withdrawals() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let senior = Address::generate(&setup.env);
+    let junior = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &junior);
+    setup.token_admin.mint(&senior, &1000i128);
+    setup.token_admin.mint(&junior, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&junior, &500i128, &Tranche::Junior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&senior, &1000i128, &Tranche::Senior);
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_senior_ratio(&setup.admin, &40000u32);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &1200i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&junior, &500i128, &Tranche::Junior);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&junior, &Tranche::Junior),
+        500i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&junior, &Tranche::Senior),
+        0i128
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&senior, &100i128, &Tranche::Senior);
+
+    assert_eq!(
+        setup.liquid_contract.client().process_withdrawals(&10u32),
+        300i128
+    );
+    assert_eq!(setup.token.balance(&junior), 250i128);
+    assert_eq!(setup.token.balance(&senior), 50i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .tranche_assets(&Tranche::Junior),
+        250i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&junior, &Tranche::Junior),
+        250i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&senior, &Tranche::Senior),
+        50i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdrawal_position(&senior, &Tranche::Senior),
+        1u32
+    );
+}
+
+#[test]
+fn test_process_senior_withdrawal_behind_blocked_junior() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let senior = Address::generate(&setup.env);
+    let junior = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &junior);
+    setup.token_admin.mint(&senior, &1000i128);
+    setup.token_admin.mint(&junior, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&junior, &500i128, &Tranche::Junior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&senior, &1000i128, &Tranche::Senior);
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_senior_ratio(&setup.admin, &20000u32);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &1200i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&junior, &500i128, &Tranche::Junior);
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&senior, &100i128, &Tranche::Senior);
+
+    assert_eq!(
+        setup.liquid_contract.client().process_withdrawals(&10u32),
+        100i128
+    );
+    assert_eq!(setup.token.balance(&junior), 0i128);
+    assert_eq!(setup.token.balance(&senior), 100i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .pending_withdrawal(&junior, &Tranche::Junior),
+        500i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .withdrawal_position(&junior, &Tranche::Junior),
+        0u32
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &150i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(setup.token.balance(&borrower), 1350i128);
+    assert_eq!(setup.token.balance(&setup.liquid_contract_id), 50i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_withdraw_with_queued_requests() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup.token_admin.mint(&lender1, &500i128);
+    setup.token_admin.mint(&lender2, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &600i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .request_withdrawal(&lender1, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .withdraw(&lender2, &100i128, &Tranche::Senior);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #45)")]
+fn test_cancel_withdrawal_without_request() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup
+        .liquid_contract
+        .client()
+        .cancel_withdrawal(&lender, &Tranche::Senior);
+}
+
+#[test]
+fn test_loan() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender1);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender2);
+
+    setup.token_admin.mock_all_auths().mint(&lender1, &10i128);
+    setup.token_admin.mock_all_auths().mint(&lender2, &10i128);
+    setup
+        .token_admin
+        .mock_all_auths()
+        .mint(&setup.liquid_contract_id, &20i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender1, &10i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender2, &10i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 20i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id = setup.liquid_contract.client().mock_all_auths().loan(
+        &borrower,
+        &10i128,
+        &LOAN_DURATION,
+        &None,
+        &None,
+    );
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 10i128);
+    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(10i128));
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(10i128));
+    assert_eq!(
+        contract_events,
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "initialize").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    setup.token.address.into_val(&setup.env),
+                ],
+                INTEREST_RATE.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    lender1.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    lender2.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender1.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
+                ],
+                10i128.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender2.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
+                ],
+                10i128.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_borrower").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                (10i128, 0i128).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_loan_negative_amount() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup.liquid_contract.client().mock_all_auths().loan(
+        &borrower,
+        &-10i128,
+        &LOAN_DURATION,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_loan_without_borrower() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup.liquid_contract.client().mock_all_auths().loan(
+        &borrower,
+        &10i128,
+        &LOAN_DURATION,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_loan_with_disable_borrower() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .set_borrower_status(&setup.admin, &borrower, &false);
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &10i128, &LOAN_DURATION, &None, &None);
+}
+
+#[test]
+fn test_request_two_loans() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender);
+
+    setup.token_admin.mock_all_auths().mint(&lender, &20i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender, &20i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 20i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    let first_loan_id = setup.liquid_contract.client().mock_all_auths().loan(
+        &borrower,
+        &10i128,
+        &LOAN_DURATION,
+        &None,
+        &None,
+    );
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 10i128);
+    assert!(setup.liquid_contract.has_loan(&borrower, first_loan_id));
+
+    let second_loan_id = setup.liquid_contract.client().mock_all_auths().loan(
+        &borrower,
+        &10i128,
+        &LOAN_DURATION,
+        &None,
+        &None,
+    );
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
+    assert!(setup.liquid_contract.has_loan(&borrower, second_loan_id));
+    assert_eq!(
+        contract_events,
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "initialize").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    setup.token.address.into_val(&setup.env),
+                ],
+                INTEREST_RATE.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    lender.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
+                ],
+                20i128.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_borrower").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    first_loan_id.into_val(&setup.env),
+                ],
+                (10i128, 0i128).into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    second_loan_id.into_val(&setup.env),
+                ],
+                (10i128, 0i128).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_set_max_open_loans_per_borrower() {
+    let setup = Setup::new();
+
+    assert_eq!(
+        setup.liquid_contract.client().max_open_loans_per_borrower(),
+        None
+    );
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &setup.admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "set_max_open_loans_per_borrower",
+                args: (setup.admin.clone(), Some(1u32)).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .set_max_open_loans_per_borrower(&setup.admin, &Some(1u32));
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup.liquid_contract.client().max_open_loans_per_borrower(),
+        Some(1u32)
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_max_open_loans").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                Some(1u32).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_request_two_loans_with_single_loan_limit() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_open_loans_per_borrower(&setup.admin, &Some(1u32));
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &20i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &20i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &10i128, &LOAN_DURATION, &None, &None);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &10i128, &LOAN_DURATION, &None, &None);
+}
+
+#[test]
+fn test_request_loan_after_repaying_with_single_loan_limit() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_open_loans_per_borrower(&setup.admin, &Some(1u32));
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &20i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &20i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let first_loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &10i128, &LOAN_DURATION, &None, &None);
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &first_loan_id, &10i128);
+
+    let second_loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &10i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &first_loan_id),
+        LoanStatus::Repaid
+    );
+    assert!(setup.liquid_contract.has_loan(&borrower, second_loan_id));
+}
+
+#[test]
+fn test_repay_loan_with_repayment_total_amount() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+
+    setup.token_admin.mint(&lender1, &5000i128);
+    setup.token_admin.mint(&lender2, &5000i128);
+    setup
+        .token_admin
+        .mint(&setup.liquid_contract_id, &10000i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &5000i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &5000i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 10000i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &10000i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
+    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(5000i128));
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(5000i128));
+
+    set_timestamp_for_20_days(&setup.env);
+
+    setup.token_admin.mint(&borrower, &10020i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &10020i128);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 10018i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(5009i128));
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(5009i128));
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Repaid
+    );
+    assert_eq!(
+        contract_events,
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "initialize").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    setup.token.address.into_val(&setup.env),
+                ],
+                INTEREST_RATE.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    lender1.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    lender2.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender1.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
+                ],
+                5000i128.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender2.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
+                ],
+                5000i128.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_borrower").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                (10000i128, 0i128).into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "repay_loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                RepaymentSplit {
+                    fees: 0i128,
+                    interest: 20i128,
+                    principal: 10000i128,
+                }
+                .into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_repay_loan_without_repayment_total_amount() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender1);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_lender(&setup.admin, &lender2);
+
+    setup.token_admin.mock_all_auths().mint(&lender1, &500i128);
+    setup.token_admin.mock_all_auths().mint(&lender2, &500i128);
+    setup
+        .token_admin
+        .mock_all_auths()
+        .mint(&setup.liquid_contract_id, &1000i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender1, &500i128, &Tranche::Senior);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender2, &500i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id = setup.liquid_contract.client().mock_all_auths().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &None,
+        &None,
+    );
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 0i128);
+    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(500i128));
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(500i128));
+
+    set_timestamp_for_20_days(&setup.env);
+
+    setup
+        .token_admin
+        .mock_all_auths()
+        .mint(&borrower, &1000i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .repay_loan(&borrower, &loan_id, &1000i128);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(501i128));
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(501i128));
+    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
+    assert_eq!(
+        setup
+            .liquid_contract
+            .read_loan_principal(&borrower, loan_id),
+        2i128
+    );
+
+    assert_eq!(
+        contract_events,
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "initialize").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    setup.token.address.into_val(&setup.env),
+                ],
+                INTEREST_RATE.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    lender1.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    lender2.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender1.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
+                ],
+                500i128.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "deposit").as_val(),
+                    lender2.into_val(&setup.env),
+                    Tranche::Senior.into_val(&setup.env),
+                ],
+                500i128.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "add_borrower").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
+                ],
+                ().into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                (1000i128, 0i128).into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "repay_loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                RepaymentSplit {
+                    fees: 0i128,
+                    interest: 2i128,
+                    principal: 998i128,
+                }
+                .into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_repay_loan_overpayment_is_capped() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &500i128);
+
+    let status = setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &1500i128);
+
+    assert_eq!(status, LoanStatus::Repaid);
+    assert_eq!(setup.token.balance(&borrower), 498i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(1002i128));
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 1002i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Repaid
+    );
+}
+
+#[test]
+fn test_clear_repaid_loans() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let repaid_loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &100i128, &LOAN_DURATION, &None, &None);
+    let open_loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &100i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &repaid_loan_id, &100i128);
+
+    let cleared = setup.liquid_contract.client().clear_repaid_loans(&borrower);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(cleared, 1u32);
+    assert!(!setup.liquid_contract.has_loan(&borrower, repaid_loan_id));
+    assert!(setup.liquid_contract.has_loan(&borrower, open_loan_id));
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "clear_repaid_loans").as_val(),
+                    borrower.into_val(&setup.env),
+                ],
+                1u32.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn test_loan_due_time_overflow() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    set_timestamp_for_20_days(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &100i128, &u64::MAX, &None, &None);
+}
+
+#[test]
+fn test_repay_in_full() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &2i128);
+
+    let amount = setup
+        .liquid_contract
+        .client()
+        .repay_in_full(&borrower, &loan_id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(amount, 1002i128);
+    assert_eq!(setup.token.balance(&borrower), 0i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(1002i128));
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Repaid
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "repay_loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                RepaymentSplit {
+                    fees: 0i128,
+                    interest: 2i128,
+                    principal: 1000i128,
+                }
+                .into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_partial_repayment_does_not_compound_interest() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+    setup.liquid_contract.execute_change(id);
+    let start_time = setup.env.ledger().timestamp();
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &(60 * 86400), &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &600i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &100i128);
+
+    let loan = setup
+        .liquid_contract
+        .client()
+        .loan_info(&borrower, &loan_id);
+
+    assert_eq!(loan.principal, 1000i128);
+    assert_eq!(loan.interest_paid, 100i128);
+    assert_eq!(loan.accrued_interest, 100i128);
+    assert_eq!(loan.last_accrual_time, start_time + 20 * 86400);
+
+    set_timestamp_for_20_days(&setup.env);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .repay_loan_amount(&borrower, &loan_id),
+        1300i128
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &500i128);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .read_loan_principal(&borrower, loan_id),
+        800i128
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "repay_loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                RepaymentSplit {
+                    fees: 0i128,
+                    interest: 300i128,
+                    principal: 200i128,
+                }
+                .into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_repayment_pays_penalty_fees_first() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().set_maturity_terms(
+        &setup.admin,
+        &MaturityTerms {
+            max_tenor: None,
+            grace_period: 0,
+            penalty_rate: 10,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &(10 * 86400), &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &11i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &11i128);
+
+    let loan = setup
+        .liquid_contract
+        .client()
+        .loan_info(&borrower, &loan_id);
+
+    assert_eq!(loan.accrued_fees, 0i128);
+    assert_eq!(loan.accrued_interest, 1i128);
+    assert_eq!(loan.interest_paid, 1i128);
+    assert_eq!(loan.principal, 1000i128);
+    assert_eq!(setup.liquid_contract.client().treasury_balance(), 1i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_repay_loan_negative_amount() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .repay_loan(&borrower, &1u64, &-10i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_repay_loan_without_borrower() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .repay_loan(&borrower, &1u64, &10i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_repay_loan_without_active_loan() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .repay_loan(&borrower, &1u64, &10i128);
+}
+
+#[test]
+fn test_repay_loan_amount() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+
+    setup.token_admin.mock_all_auths().mint(&lender, &1000i128);
+    setup
+        .token_admin
+        .mock_all_auths()
+        .mint(&setup.liquid_contract_id, &1000i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id = setup.liquid_contract.client().mock_all_auths().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &None,
+        &None,
+    );
+
+    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
+    set_timestamp_for_20_days(&setup.env);
+
+    let loan_amount = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .repay_loan_amount(&borrower, &loan_id);
+
+    assert_eq!(loan_amount, 1002i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_repay_loan_amount_without_borrower() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .repay_loan_amount(&borrower, &1u64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_repay_loan_amount_without_active_loan() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan_amount(&borrower, &1u64);
+}
+
+#[test]
+fn test_set_interest_rate() {
+    let setup = Setup::new();
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &setup.admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "set_interest_rate",
+                args: (setup.admin.clone(), 10u32).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .set_interest_rate(&setup.admin, &10u32);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.client().interest_rate(), 10u32);
+    assert_eq!(
+        contract_events,
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "initialize").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    setup.token.address.into_val(&setup.env),
+                ],
+                INTEREST_RATE.into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "queue_change").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    id.into_val(&setup.env),
+                ],
+                (ParameterChange::InterestRate(10u32), 86400u64).into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "execute_change").as_val(),
+                    id.into_val(&setup.env),
+                ],
+                ParameterChange::InterestRate(10u32).into_val(&setup.env)
+            ),
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_interest_rate").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                10u32.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_set_interest_rate_with_fake_admin() {
+    let setup = Setup::new();
+    let fake_admin = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &fake_admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "set_interest_rate",
+                args: (fake_admin.clone(), 10u32).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .set_interest_rate(&fake_admin, &10u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_set_invalid_interest_rate() {
+    let setup = Setup::new();
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_interest_rate(&setup.admin, &10_001u32);
+}
+
+#[test]
+fn test_loan_keeps_interest_rate_after_rate_change() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+    setup.liquid_contract.execute_change(id);
+    set_timestamp_for_20_days(&setup.env);
+
+    let loan_amount = setup
+        .liquid_contract
+        .client()
+        .repay_loan_amount(&borrower, &loan_id);
+
+    assert_eq!(loan_amount, 1002i128);
+}
+
+#[test]
+fn test_set_interest_rate_model() {
+    let setup = Setup::new();
+    let model = InterestRateModel {
+        base_rate: 10,
+        slope1: 100,
+        kink: 8000,
+        slope2: 1000,
+    };
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_interest_rate_model(&setup.admin, &model);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.client().interest_rate_model(), model);
+    assert_eq!(setup.liquid_contract.client().interest_rate(), 10u32);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_interest_rate_model").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                model.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_set_interest_rate_model_with_invalid_kink() {
+    let setup = Setup::new();
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_interest_rate_model(
+            &setup.admin,
+            &InterestRateModel {
+                base_rate: 10,
+                slope1: 100,
+                kink: 0,
+                slope2: 1000,
+            },
+        );
+}
+
+#[test]
+fn test_loan_with_utilization_based_rate() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().set_interest_rate_model(
+        &setup.admin,
+        &InterestRateModel {
+            base_rate: 10,
+            slope1: 100,
+            kink: 8000,
+            slope2: 1000,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    assert_eq!(setup.liquid_contract.client().utilization(), 0u32);
+    assert_eq!(setup.liquid_contract.client().borrow_rate(), 10u32);
+
+    let first_loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &500i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(setup.liquid_contract.client().utilization(), 5000u32);
+    assert_eq!(setup.liquid_contract.client().borrow_rate(), 72u32);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .read_loan_interest_rate(&borrower, first_loan_id),
+        72u32
+    );
+
+    let second_loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &400i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(setup.liquid_contract.client().utilization(), 9000u32);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .read_loan_interest_rate(&borrower, second_loan_id),
+        610u32
+    );
+}
+
+#[test]
+fn test_set_maturity_terms() {
+    let setup = Setup::new();
+    let terms = MaturityTerms {
+        max_tenor: Some(90 * 86400),
+        grace_period: 5 * 86400,
+        penalty_rate: 10,
+    };
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_maturity_terms(&setup.admin, &terms);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.client().maturity_terms(), terms);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_maturity_terms").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                terms.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_loan_exceeds_pool_max_tenor() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().set_maturity_terms(
+        &setup.admin,
+        &MaturityTerms {
+            max_tenor: Some(10 * 86400),
+            grace_period: 0,
+            penalty_rate: 0,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &500i128, &LOAN_DURATION, &None, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn test_loan_without_duration() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &500i128, &0u64, &None, &None);
+}
+
+#[test]
+fn test_loan_status_after_due_time() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let start_time = setup.env.ledger().timestamp();
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &(10 * 86400), &None, &None);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Active
+    );
+
+    set_timestamp_for_20_days(&setup.env);
+
+    let loan = setup
+        .liquid_contract
+        .client()
+        .loan_info(&borrower, &loan_id);
+
+    assert_eq!(loan.due_time, start_time + 10 * 86400);
+    assert_eq!(loan.status, LoanStatus::Overdue);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Overdue
+    );
+}
+
+#[test]
+fn test_repay_loan_amount_with_penalty_after_grace_period() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().set_maturity_terms(
+        &setup.admin,
+        &MaturityTerms {
+            max_tenor: None,
+            grace_period: 5 * 86400,
+            penalty_rate: 10,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &(10 * 86400), &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+
+    let loan_amount = setup
+        .liquid_contract
+        .client()
+        .repay_loan_amount(&borrower, &loan_id);
+
+    assert_eq!(loan_amount, 1007i128);
+}
+
+#[test]
+fn test_repay_loan_returns_loan_status() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .repay_loan(&borrower, &loan_id, &400i128),
+        LoanStatus::Active
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .repay_loan(&borrower, &loan_id, &600i128),
+        LoanStatus::Repaid
+    );
+}
+
+#[test]
+fn test_mark_default() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup.token_admin.mint(&lender1, &500i128);
+    setup.token_admin.mint(&lender2, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Defaulted
+    );
+    assert_eq!(
+        setup.liquid_contract.client().lender_loss(&lender1),
+        500i128
+    );
+    assert_eq!(
+        setup.liquid_contract.client().lender_loss(&lender2),
+        500i128
+    );
+    assert_eq!(setup.liquid_contract.client().utilization(), 0u32);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "mark_default").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                1000i128.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_mark_default_with_fake_admin() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+    let fake_admin = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &fake_admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "mark_default",
+                args: (fake_admin.clone(), borrower.clone(), 1u64).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .mark_default(&fake_admin, &borrower, &1u64);
+}
+
+#[test]
+fn test_mark_default_settles_loss_on_share_changes() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+    let lender3 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender3);
+    setup.token_admin.mint(&lender1, &1000i128);
+    setup.token_admin.mint(&lender2, &1000i128);
+    setup.token_admin.mint(&lender3, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+    setup
+        .liquid_contract
+        .client()
+        .transfer(&lender1, &lender3, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender3, &1000i128, &Tranche::Senior);
+
+    assert_eq!(setup.liquid_contract.client().total_assets(), 2000i128);
+    assert_eq!(setup.liquid_contract.client().balance(&lender3), 2500i128);
+    assert_eq!(
+        setup.liquid_contract.client().lender_loss(&lender1),
+        500i128
+    );
+    assert_eq!(
+        setup.liquid_contract.client().lender_loss(&lender2),
+        500i128
+    );
+    assert_eq!(setup.liquid_contract.client().lender_loss(&lender3), 0i128);
+}
+
+#[test]
+fn test_lender_summary() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup.token_admin.mint(&lender1, &100_000i128);
+    setup.token_admin.mint(&lender2, &100_000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &100_000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &100_000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup.token_admin.mint(&borrower, &100_000i128);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &100_000i128, &LOAN_DURATION, &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_in_full(&borrower, &loan_id);
+
+    setup
+        .liquid_contract
+        .client()
+        .withdraw(&lender1, &50_000i128, &Tranche::Senior);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &100_000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    assert_eq!(
+        setup.liquid_contract.client().lender_summary(&lender1),
+        LenderSummary {
+            active: true,
+            deposited: 100_000,
+            withdrawn: 50_000,
+            interest_earned: 90,
+            losses: 33_352,
+            value: 16_736,
+        }
+    );
+    assert_eq!(
+        setup.liquid_contract.client().lender_summary(&lender2),
+        LenderSummary {
+            active: true,
+            deposited: 100_000,
+            withdrawn: 0,
+            interest_earned: 90,
+            losses: 66_647,
+            value: 33_443,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_lender_summary_not_registered() {
+    let setup = Setup::new();
+    let lender = Address::generate(&setup.env);
+
+    setup.liquid_contract.client().lender_summary(&lender);
+}
+
+#[test]
+fn test_loan_with_many_lenders() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let mut lenders = Vec::new(&setup.env);
+
+    setup.env.budget().reset_unlimited();
+
+    for _ in 0..100 {
+        let lender = Address::generate(&setup.env);
+
+        setup
+            .liquid_contract
+            .client()
+            .add_lender(&setup.admin, &lender);
+        setup.token_admin.mint(&lender, &100i128);
+        setup
+            .liquid_contract
+            .client()
+            .deposit(&lender, &100i128, &Tranche::Senior);
+        lenders.push_back(lender);
+    }
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup.env.budget().reset_default();
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &5000i128, &LOAN_DURATION, &None, &None);
+
+    setup.env.budget().reset_default();
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    setup.env.budget().reset_unlimited();
+
+    for lender in lenders.iter() {
+        assert_eq!(setup.liquid_contract.client().lender_loss(&lender), 50i128);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #28)")]
+fn test_repay_defaulted_loan() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &1000i128);
+}
+
+#[test]
+fn test_recover() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender1);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender2);
+    setup.token_admin.mint(&lender1, &500i128);
+    setup.token_admin.mint(&lender2, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender1, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender2, &500i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    setup.token_admin.mint(&setup.admin, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .recover(&setup.admin, &borrower, &loan_id, &400i128);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 400i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(200i128));
+    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(200i128));
+    assert_eq!(
+        setup.liquid_contract.client().lender_loss(&lender1),
+        300i128
+    );
+    assert_eq!(
+        setup.liquid_contract.client().lender_loss(&lender2),
+        300i128
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "recover").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                400i128.into_val(&setup.env)
+            )
+        ]
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .recover(&setup.admin, &borrower, &loan_id, &600i128);
+
+    assert_eq!(setup.liquid_contract.client().lender_loss(&lender1), 0i128);
+    assert_eq!(setup.liquid_contract.client().lender_loss(&lender2), 0i128);
+    assert!(!setup.liquid_contract.has_loan(&borrower, loan_id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #29)")]
+fn test_recover_active_loan() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .recover(&setup.admin, &borrower, &loan_id, &100i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_recover_more_than_loss() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    setup
+        .liquid_contract
+        .client()
+        .recover(&setup.admin, &borrower, &loan_id, &1001i128);
+}
+
+#[test]
+fn test_set_collateral_token() {
+    let setup = Setup::new();
+    let (collateral_token, _) = create_token_contract(&setup.env, &setup.admin);
+    let config = COLLATERAL_CONFIG;
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_collateral_token(&setup.admin, &collateral_token.address, &config);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .collateral_config(&collateral_token.address),
+        config
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_collateral_token").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    collateral_token.address.into_val(&setup.env),
+                ],
+                config.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_remove_collateral_token() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let (collateral_token, _) = create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .remove_collateral_token(&setup.admin, &collateral_token.address);
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .collateral_config(&collateral_token.address);
+}
+
+#[test]
+fn test_loan_with_collateral() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    setup.create_oracle(&collateral_token.address);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &2000i128);
+
+    let collateral = Collateral {
+        token: collateral_token.address.clone(),
+        amount: 2000i128,
+    };
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(collateral.clone()),
+        &None,
+    );
+
+    assert_eq!(collateral_token.balance(&borrower), 0i128);
+    assert_eq!(
+        collateral_token.balance(&setup.liquid_contract_id),
+        2000i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_collateral(&borrower, &loan_id),
+        Some(collateral)
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &1000i128);
+
+    assert_eq!(collateral_token.balance(&borrower), 2000i128);
+    assert_eq!(collateral_token.balance(&setup.liquid_contract_id), 0i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_loan_with_insufficient_collateral() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    setup.create_oracle(&collateral_token.address);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &1000i128);
+
+    setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 1000i128,
+        }),
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_loan_with_collateral_token_not_allowed() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &2000i128);
+
+    setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 2000i128,
+        }),
+        &None,
+    );
+}
+
+#[test]
+fn test_seize_collateral() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    setup.create_oracle(&collateral_token.address);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &2000i128);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 2000i128,
+        }),
+        &None,
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .convert_to_assets(&1000i128, &Tranche::Senior),
+        0i128
+    );
+
+    setup.token_admin.mint(&setup.admin, &1000i128);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .seize_collateral(&setup.admin, &borrower, &loan_id),
+        1000i128
+    );
+
+    assert_eq!(collateral_token.balance(&setup.admin), 1000i128);
+    assert_eq!(collateral_token.balance(&borrower), 1000i128);
+    assert_eq!(collateral_token.balance(&setup.liquid_contract_id), 0i128);
+    assert_eq!(setup.token.balance(&setup.admin), 0i128);
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .convert_to_assets(&1000i128, &Tranche::Senior),
+        1000i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_collateral(&borrower, &loan_id),
+        None
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #29)")]
+fn test_seize_collateral_of_active_loan() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    setup
+        .liquid_contract
+        .client()
+        .seize_collateral(&setup.admin, &borrower, &loan_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn test_set_collateral_token_with_invalid_liquidation_threshold() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let (collateral_token, _) = create_token_contract(&setup.env, &setup.admin);
+
+    setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &CollateralConfig {
+            ltv: 5000,
+            liquidation_threshold: 4000,
+            liquidation_bonus: 500,
+        },
+    );
+}
+
+#[test]
+fn test_set_oracle() {
+    let setup = Setup::new();
+    let oracle = create_oracle_contract(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_oracle(&setup.admin, &oracle.address);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup.liquid_contract.client().oracle(),
+        Some(oracle.address.clone())
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_oracle").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                oracle.address.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_loan_with_collateral_without_oracle() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &2000i128);
+
+    setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 2000i128,
+        }),
+        &None,
+    );
+}
+
+#[test]
+fn test_loan_with_collateral_valued_by_oracle() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    let oracle = setup.create_oracle(&collateral_token.address);
+    set_oracle_price(&oracle, &collateral_token.address, 2 * ORACLE_PRICE);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &1000i128);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 1000i128,
+        }),
+        &None,
+    );
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .health_factor(&borrower, &loan_id),
+        16000i128
+    );
+
+    set_oracle_price(&oracle, &collateral_token.address, ORACLE_PRICE);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .health_factor(&borrower, &loan_id),
+        8000i128
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn test_health_factor_with_stale_price() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    let oracle = setup.create_oracle(&collateral_token.address);
+    set_oracle_price(&oracle, &collateral_token.address, 2 * ORACLE_PRICE);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &1000i128);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 1000i128,
+        }),
+        &None,
+    );
+
+    setup.env.ledger().set_timestamp(
+        setup.env.ledger().timestamp() + setup.liquid_contract.client().max_price_age() + 1,
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .health_factor(&borrower, &loan_id);
+}
+
+#[test]
+fn test_set_max_price_age() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    let oracle = setup.create_oracle(&collateral_token.address);
+    set_oracle_price(&oracle, &collateral_token.address, 2 * ORACLE_PRICE);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &1000i128);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 1000i128,
+        }),
+        &None,
+    );
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_price_age(&setup.admin, &86400u64);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    // The timelock delay has already aged the oracle prices by a day.
+    assert_eq!(setup.liquid_contract.client().max_price_age(), 86400u64);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .health_factor(&borrower, &loan_id),
+        16000i128
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_max_price_age").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                86400u64.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_liquidate() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let liquidator = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    let oracle = setup.create_oracle(&collateral_token.address);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &2000i128);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 2000i128,
+        }),
+        &None,
+    );
+
+    set_oracle_price(&oracle, &collateral_token.address, ORACLE_PRICE / 2);
+    setup.token_admin.mint(&liquidator, &500i128);
+
+    let seized_amount =
+        setup
+            .liquid_contract
+            .client()
+            .liquidate(&liquidator, &borrower, &loan_id, &500i128);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(seized_amount, 1050i128);
+    assert_eq!(collateral_token.balance(&liquidator), 1050i128);
+    assert_eq!(setup.token.balance(&liquidator), 0i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .read_loan_principal(&borrower, loan_id),
+        500i128
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_collateral(&borrower, &loan_id),
+        Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 950i128,
+        })
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "liquidate").as_val(),
+                    liquidator.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                (500i128, 1050i128).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #37)")]
+fn test_liquidate_healthy_loan() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let liquidator = Address::generate(&setup.env);
+    let (collateral_token, collateral_token_admin) =
+        create_token_contract(&setup.env, &setup.admin);
+
+    let id = setup.liquid_contract.client().set_collateral_token(
+        &setup.admin,
+        &collateral_token.address,
+        &COLLATERAL_CONFIG,
+    );
+    setup.liquid_contract.execute_change(id);
+    setup.create_oracle(&collateral_token.address);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    collateral_token_admin.mint(&borrower, &2000i128);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &1000i128,
+        &LOAN_DURATION,
+        &Some(Collateral {
+            token: collateral_token.address.clone(),
+            amount: 2000i128,
+        }),
+        &None,
+    );
+
+    setup.token_admin.mint(&liquidator, &500i128);
+    setup
+        .liquid_contract
+        .client()
+        .liquidate(&liquidator, &borrower, &loan_id, &500i128);
+}
+
+#[test]
+fn test_loan_with_repayment_schedule() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let start_time = setup.env.ledger().timestamp();
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &900i128,
+        &LOAN_DURATION,
+        &None,
+        &Some(RepaymentSchedule {
+            installments: 3,
+            period: 10 * 86400,
+        }),
+    );
+
+    let installment = |number: u64, paid: i128| Installment {
+        due_time: start_time + number * 10 * 86400,
+        amount: 300i128,
+        paid,
+    };
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_schedule(&borrower, &loan_id),
+        LoanSchedule {
+            installments: vec![
+                &setup.env,
+                installment(1, 0),
+                installment(2, 0),
+                installment(3, 0)
+            ],
+            paid: 0i128,
+            due: 900i128,
+            overdue: 0i128,
+            accrued: 0i128,
+        }
+    );
+
+    setup.token_admin.mint(&borrower, &400i128);
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &400i128);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_schedule(&borrower, &loan_id),
+        LoanSchedule {
+            installments: vec![
+                &setup.env,
+                installment(1, 300),
+                installment(2, 100),
+                installment(3, 0)
+            ],
+            paid: 400i128,
+            due: 500i128,
+            overdue: 0i128,
+            accrued: 0i128,
+        }
+    );
+}
+
+#[test]
+fn test_missed_installment_makes_loan_overdue() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &900i128,
+        &LOAN_DURATION,
+        &None,
+        &Some(RepaymentSchedule {
+            installments: 2,
+            period: 15 * 86400,
+        }),
+    );
+
+    set_timestamp_for_20_days(&setup.env);
+
+    let schedule = setup
+        .liquid_contract
+        .client()
+        .loan_schedule(&borrower, &loan_id);
+
+    assert_eq!(schedule.overdue, 451i128);
+    assert_eq!(schedule.due, 450i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Overdue
+    );
+
+    setup.token_admin.mint(&borrower, &451i128);
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &451i128);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Active
+    );
+}
+
+#[test]
+fn test_installments_include_interest() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    let start_time = setup.env.ledger().timestamp();
+
+    let loan_id = setup.liquid_contract.client().loan(
+        &borrower,
+        &900i128,
+        &LOAN_DURATION,
+        &None,
+        &Some(RepaymentSchedule {
+            installments: 2,
+            period: 15 * 86400,
+        }),
+    );
+
+    setup.env.ledger().set_timestamp(start_time + 15 * 86400);
+
+    let schedule = setup
+        .liquid_contract
+        .client()
+        .loan_schedule(&borrower, &loan_id);
+
+    assert_eq!(schedule.installments.get_unchecked(0).amount, 451i128);
+    assert_eq!(schedule.installments.get_unchecked(1).amount, 450i128);
+    assert_eq!(schedule.accrued, 1i128);
+
+    setup.token_admin.mint(&borrower, &451i128);
+    setup
+        .liquid_contract
+        .client()
+        .repay_loan(&borrower, &loan_id, &451i128);
+
+    setup
+        .env
+        .ledger()
+        .set_timestamp(start_time + 15 * 86400 + 1);
+
+    let schedule = setup
+        .liquid_contract
+        .client()
+        .loan_schedule(&borrower, &loan_id);
+
+    assert_eq!(schedule.installments.get_unchecked(0).paid, 451i128);
+    assert_eq!(schedule.overdue, 0i128);
+    assert_eq!(schedule.accrued, 0i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .loan_status(&borrower, &loan_id),
+        LoanStatus::Active
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #38)")]
+fn test_loan_with_too_many_installments() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup.liquid_contract.client().loan(
+        &borrower,
+        &900i128,
+        &LOAN_DURATION,
+        &None,
+        &Some(RepaymentSchedule {
+            installments: 121,
+            period: 1,
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #38)")]
+fn test_loan_with_overflowing_repayment_schedule() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup.liquid_contract.client().loan(
+        &borrower,
+        &900i128,
+        &LOAN_DURATION,
+        &None,
+        &Some(RepaymentSchedule {
+            installments: 2,
+            period: u64::MAX,
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #38)")]
+fn test_loan_with_invalid_repayment_schedule() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup.liquid_contract.client().loan(
+        &borrower,
+        &900i128,
+        &LOAN_DURATION,
+        &None,
+        &Some(RepaymentSchedule {
+            installments: 4,
+            period: 10 * 86400,
+        }),
+    );
+}
+
+#[test]
+fn test_set_origination_fee() {
+    let setup = Setup::new();
+    let origination_fee = OriginationFee {
+        rate: 100,
+        mode: OriginationFeeMode::Financed,
+    };
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_origination_fee(&setup.admin, &origination_fee);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup.liquid_contract.client().origination_fee(),
+        origination_fee
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_origination_fee").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                origination_fee.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_set_invalid_origination_fee() {
+    let setup = Setup::new();
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_origination_fee(
+            &setup.admin,
+            &OriginationFee {
+                rate: 10001,
+                mode: OriginationFeeMode::Deducted,
+            },
+        );
+}
+
+#[test]
+fn test_loan_with_deducted_origination_fee() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().set_origination_fee(
+        &setup.admin,
+        &OriginationFee {
+            rate: 100,
+            mode: OriginationFeeMode::Deducted,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.token.balance(&borrower), 990i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .read_loan_principal(&borrower, loan_id),
+        1000i128
+    );
+    assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(1009i128));
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 9i128);
+    assert_eq!(setup.liquid_contract.client().treasury_balance(), 1i128);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "loan").as_val(),
+                    borrower.into_val(&setup.env),
+                    loan_id.into_val(&setup.env),
+                ],
+                (1000i128, 10i128).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_loan_with_financed_origination_fee() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().set_origination_fee(
+        &setup.admin,
+        &OriginationFee {
+            rate: 100,
+            mode: OriginationFeeMode::Financed,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &2000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &2000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(setup.token.balance(&borrower), 1000i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .repay_loan_amount(&borrower, &loan_id),
+        1010i128
+    );
+    assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(2009i128));
+    assert_eq!(setup.liquid_contract.read_contract_balance(), 999i128);
+    assert_eq!(setup.liquid_contract.client().treasury_balance(), 1i128);
+}
+
+#[test]
+fn test_set_protocol_fee() {
+    let setup = Setup::new();
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_protocol_fee(&setup.admin, &2000u32);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.client().protocol_fee(), 2000u32);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "set_protocol_fee").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                2000u32.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #39)")]
+fn test_set_invalid_protocol_fee() {
+    let setup = Setup::new();
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_protocol_fee(&setup.admin, &10001u32);
+}
+
+#[test]
+fn test_transfer_admin() {
+    let setup = Setup::new();
+    let new_admin = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .propose_admin(&new_admin);
+
+    assert_eq!(setup.liquid_contract.client().pending_admin(), None);
+
+    setup.liquid_contract.execute_change(id);
+
+    assert_eq!(setup.liquid_contract.client().admin(), setup.admin);
+    assert_eq!(
+        setup.liquid_contract.client().pending_admin(),
+        Some(new_admin.clone())
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &new_admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "accept_admin",
+                args: ().into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .accept_admin();
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.client().admin(), new_admin);
+    assert_eq!(setup.liquid_contract.client().pending_admin(), None);
+    assert_eq!(setup.liquid_contract.read_admin(), Ok(new_admin.clone()));
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "accept_admin").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                new_admin.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_accept_admin_requires_new_admin_auth() {
+    let setup = Setup::new();
+    let new_admin = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .propose_admin(&new_admin);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &setup.admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "accept_admin",
+                args: ().into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .accept_admin();
+}
+
+#[test]
+fn test_cancel_admin_proposal() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let new_admin = Address::generate(&setup.env);
+
+    let id = setup.liquid_contract.client().propose_admin(&new_admin);
+    setup.liquid_contract.execute_change(id);
+    setup.liquid_contract.client().cancel_admin_proposal();
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.client().pending_admin(), None);
+    assert_eq!(setup.liquid_contract.client().admin(), setup.admin);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "cancel_admin_proposal").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                new_admin.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #53)")]
+fn test_accept_admin_without_proposal() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    setup.liquid_contract.client().accept_admin();
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #53)")]
+fn test_accept_admin_before_delay() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let new_admin = Address::generate(&setup.env);
+
+    setup.liquid_contract.client().propose_admin(&new_admin);
+    setup.liquid_contract.client().accept_admin();
+}
+
+#[test]
+fn test_grant_role() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let operator = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .grant_role(&Role::Operator, &operator);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert!(setup
+        .liquid_contract
+        .client()
+        .has_role(&Role::Operator, &operator));
+    assert!(!setup
+        .liquid_contract
+        .client()
+        .has_role(&Role::RiskManager, &operator));
+    assert!(setup
+        .liquid_contract
+        .client()
+        .has_role(&Role::RiskManager, &setup.admin));
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "grant_role").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    operator.into_val(&setup.env),
+                ],
+                Role::Operator.into_val(&setup.env)
+            )
+        ]
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&operator, &lender);
+
+    assert!(setup.liquid_contract.read_lender(&lender).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_call_with_wrong_role() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let operator = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .grant_role(&Role::Operator, &operator);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&operator, &10u32);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_revoke_role() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let treasurer = Address::generate(&setup.env);
+    let treasury = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .grant_role(&Role::Treasurer, &treasurer);
+    setup.liquid_contract.execute_change(id);
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_treasury(&treasurer, &treasury);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .revoke_role(&Role::Treasurer, &treasurer);
+
+    assert!(!setup
+        .liquid_contract
+        .client()
+        .has_role(&Role::Treasurer, &treasurer));
+
+    setup
+        .liquid_contract
+        .client()
+        .set_treasury(&treasurer, &treasury);
+}
+
+#[test]
+fn test_pause() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let pauser = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .grant_role(&Role::Pauser, &pauser);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .pause(&pauser, &Operation::Deposit);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup.liquid_contract.client().pause_status(),
+        PauseStatus {
+            deposit: true,
+            loan: false,
+            withdraw: false,
+        }
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "pause").as_val(),
+                    pauser.into_val(&setup.env),
+                ],
+                Operation::Deposit.into_val(&setup.env)
+            )
+        ]
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .unpause(&pauser, &Operation::Deposit);
+
+    assert_eq!(
+        setup.liquid_contract.client().pause_status(),
+        PauseStatus {
+            deposit: false,
+            loan: false,
+            withdraw: false,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #55)")]
+fn test_deposit_paused() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .pause(&setup.admin, &Operation::Deposit);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #55)")]
+fn test_loan_paused() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .pause(&setup.admin, &Operation::Loan);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &500i128, &LOAN_DURATION, &None, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #55)")]
+fn test_redeem_paused() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .pause(&setup.admin, &Operation::Withdraw);
+    setup
+        .liquid_contract
+        .client()
+        .redeem(&lender, &100i128, &Tranche::Senior);
+}
+
+#[test]
+fn test_repay_loan_while_paused() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &500i128, &LOAN_DURATION, &None, &None);
+
+    for operation in [Operation::Deposit, Operation::Loan, Operation::Withdraw] {
+        setup
+            .liquid_contract
+            .client()
+            .pause(&setup.admin, &operation);
+    }
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .repay_loan(&borrower, &loan_id, &500i128),
+        LoanStatus::Repaid
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .unpause(&setup.admin, &Operation::Withdraw);
+    setup
+        .liquid_contract
+        .client()
+        .withdraw(&lender, &100i128, &Tranche::Senior);
+
+    assert_eq!(setup.token.balance(&lender), 100i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_pause_without_role() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let operator = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .grant_role(&Role::Operator, &operator);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .pause(&operator, &Operation::Loan);
+}
+
+#[test]
+fn test_migrate() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let loan = LegacyLoan {
+        id: 1,
+        amount: 1000,
+        start_time: setup.env.ledger().timestamp(),
+        contributions: Map::from_array(&setup.env, [(lender.clone(), 1_000_000_000i64)]),
+    };
+
+    setup.liquid_contract.remove_schema_version();
+    setup.token_admin.mint(&setup.liquid_contract_id, &500i128);
+    setup.liquid_contract.write_legacy_lender(&lender, 500i128);
+    setup.liquid_contract.write_legacy_borrower(&borrower, true);
+    setup
+        .liquid_contract
+        .write_legacy_loans(&borrower, &vec![&setup.env, loan]);
+
+    assert_eq!(setup.liquid_contract.client().schema_version(), 1u32);
+
+    setup.liquid_contract.client().migrate(
+        &vec![&setup.env, lender.clone()],
+        &vec![&setup.env, borrower.clone()],
+    );
+
+    assert_eq!(setup.liquid_contract.client().schema_version(), 1u32);
+
+    setup.liquid_contract.client().finalize_migration();
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(setup.liquid_contract.client().schema_version(), 2u32);
+    assert_eq!(setup.liquid_contract.client().balance(&lender), 1500i128);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .total_shares(&Tranche::Senior),
+        1500i128
+    );
+    assert!(!setup.liquid_contract.has_legacy_contributions());
+    assert_eq!(
+        setup.liquid_contract.read_borrower(&borrower),
+        Ok(Borrower {
+            active: true,
+            terms: BorrowerTerms {
+                interest_rate: None,
+                max_principal: None,
+                max_tenor: None,
+                credit_limit: None,
+            },
+        })
+    );
+    assert_eq!(
+        setup.liquid_contract.read_loan_principal(&borrower, 1),
+        1000i128
+    );
+    assert_eq!(setup.liquid_contract.client().total_assets(), 1500i128);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "migrate").as_val(),
+                    setup.admin.into_val(&setup.env),
+                ],
+                2u32.into_val(&setup.env)
+            )
+        ]
+    );
+
+    set_timestamp_for_20_days(&setup.env);
+
+    assert_eq!(
+        setup.liquid_contract.client().loan_status(&borrower, &1u64),
+        LoanStatus::Active
+    );
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .repay_loan_amount(&borrower, &1u64),
+        1002i128
+    );
+
+    setup
+        .liquid_contract
+        .client()
+        .withdraw(&lender, &500i128, &Tranche::Senior);
+
+    assert_eq!(setup.token.balance(&lender), 500i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #57)")]
+fn test_migrate_current_version() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    setup
+        .liquid_contract
+        .client()
+        .migrate(&vec![&setup.env], &vec![&setup.env]);
+}
+
+#[test]
+fn test_migrate_in_batches() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender1 = Address::generate(&setup.env);
+    let lender2 = Address::generate(&setup.env);
+    let loan = LegacyLoan {
+        id: 1,
+        amount: 1000,
+        start_time: setup.env.ledger().timestamp(),
+        contributions: Map::from_array(
+            &setup.env,
+            [
+                (lender1.clone(), 250_000_000i64),
+                (lender2.clone(), 750_000_000i64),
+            ],
+        ),
+    };
+
+    setup.liquid_contract.remove_schema_version();
+    setup.token_admin.mint(&setup.liquid_contract_id, &1000i128);
+    setup.liquid_contract.write_legacy_lender(&lender1, 500i128);
+    setup.liquid_contract.write_legacy_lender(&lender2, 500i128);
+    setup.liquid_contract.write_legacy_borrower(&borrower, true);
+    setup
+        .liquid_contract
+        .write_legacy_loans(&borrower, &vec![&setup.env, loan]);
+
+    setup
+        .liquid_contract
+        .client()
+        .migrate(&vec![&setup.env, lender1.clone()], &vec![&setup.env]);
+    setup
+        .liquid_contract
+        .client()
+        .migrate(&vec![&setup.env], &vec![&setup.env, borrower.clone()]);
+    setup.liquid_contract.client().migrate(
+        &vec![&setup.env, lender1.clone(), lender2.clone()],
+        &vec![&setup.env, borrower.clone()],
+    );
+
+    assert_eq!(setup.liquid_contract.client().balance(&lender1), 750i128);
+    assert_eq!(setup.liquid_contract.client().balance(&lender2), 1250i128);
+
+    setup.liquid_contract.client().finalize_migration();
+
+    assert_eq!(setup.liquid_contract.client().schema_version(), 2u32);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .total_shares(&Tranche::Senior),
+        2000i128
+    );
+    assert_eq!(setup.liquid_contract.client().total_assets(), 2000i128);
+    assert!(!setup.liquid_contract.has_legacy_contributions());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #57)")]
+fn test_finalize_migration_twice() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    setup.liquid_contract.remove_schema_version();
+    setup.liquid_contract.client().finalize_migration();
+    setup.liquid_contract.client().finalize_migration();
+}
+
+#[test]
+fn test_queue_change() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    assert_eq!(setup.liquid_contract.client().min_delay(), 86400u64);
+
+    let id = setup.liquid_contract.client().set_min_delay(&172800u64);
+    setup.liquid_contract.execute_change(id);
+
+    assert_eq!(setup.liquid_contract.client().min_delay(), 172800u64);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup.liquid_contract.client().queued_changes(),
+        vec![
+            &setup.env,
+            QueuedChange {
+                id,
+                caller: setup.admin.clone(),
+                change: ParameterChange::InterestRate(100u32),
+                execution_time: 259200u64,
+            }
+        ]
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "queue_change").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    id.into_val(&setup.env),
+                ],
+                (ParameterChange::InterestRate(100u32), 259200u64).into_val(&setup.env)
+            )
+        ]
+    );
+
+    setup.env.ledger().set_timestamp(259200u64);
+    setup.liquid_contract.execute_change(id);
+
+    assert_eq!(setup.liquid_contract.client().interest_rate(), 100u32);
+    assert_eq!(
+        setup.liquid_contract.client().queued_changes(),
+        vec![&setup.env]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #52)")]
+fn test_execute_change_before_delay() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+
+    setup.env.ledger().set_timestamp(86399u64);
+    setup.liquid_contract.client().execute_change(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #56)")]
+fn test_set_min_delay_below_floor() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    setup.liquid_contract.client().set_min_delay(&86399u64);
+}
+
+#[test]
+fn test_queued_changes_after_cancel() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+
+    let first = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+    let second = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &200u32);
+    let third = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &300u32);
+
+    setup
+        .liquid_contract
+        .client()
+        .cancel_change(&setup.admin, &second);
+
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .queued_changes()
+            .iter()
+            .map(|queued| queued.id)
+            .collect::<std::vec::Vec<u64>>(),
+        [first, third]
+    );
+
+    setup.liquid_contract.execute_change(first);
+
+    assert_eq!(setup.liquid_contract.client().interest_rate(), 100u32);
+    assert_eq!(
+        setup
+            .liquid_contract
+            .client()
+            .queued_changes()
+            .iter()
+            .map(|queued| queued.id)
+            .collect::<std::vec::Vec<u64>>(),
+        [third]
+    );
+}
+
+#[test]
+fn test_upgrade_is_queued() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let new_wasm_hash = BytesN::from_array(&setup.env, &[1u8; 32]);
+
+    let id = setup.liquid_contract.client().upgrade(&new_wasm_hash);
+
+    assert_eq!(
+        setup.liquid_contract.client().queued_changes(),
+        vec![
+            &setup.env,
+            QueuedChange {
+                id,
+                caller: setup.admin.clone(),
+                change: ParameterChange::Upgrade(new_wasm_hash),
+                execution_time: 86400u64,
+            }
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #51)")]
+fn test_cancel_change() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let treasury = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_treasury(&setup.admin, &treasury);
+    setup
+        .liquid_contract
+        .client()
+        .cancel_change(&setup.admin, &id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                setup.liquid_contract_id.clone(),
+                vec![
+                    &setup.env,
+                    *Symbol::new(&setup.env, "cancel_change").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    id.into_val(&setup.env),
+                ],
+                ParameterChange::Treasury(treasury).into_val(&setup.env)
+            )
+        ]
+    );
+    assert_eq!(
+        setup.liquid_contract.client().queued_changes(),
+        vec![&setup.env]
+    );
+
+    setup.liquid_contract.client().execute_change(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_cancel_change_with_wrong_role() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let operator = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .grant_role(&Role::Operator, &operator);
+    setup.liquid_contract.execute_change(id);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_interest_rate(&setup.admin, &100u32);
+    setup.liquid_contract.client().cancel_change(&operator, &id);
+}
 
-    set_timestamp_for_20_days(&setup.env);
+#[test]
+fn test_set_treasury() {
+    let setup = Setup::new();
+    let treasury = Address::generate(&setup.env);
 
-    setup
-        .token_admin
-        .mock_all_auths()
-        .mint(&borrower, &1000i128);
+    assert_eq!(setup.liquid_contract.client().treasury(), setup.admin);
 
-    setup
+    let id = setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .repay_loan(&borrower, &loan_id, &1000i128);
+        .set_treasury(&setup.admin, &treasury);
+    setup.liquid_contract.execute_change(id);
 
     let contract_events = setup.liquid_contract.get_contract_events();
 
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
-    assert_eq!(setup.liquid_contract.read_lender(&lender1), Ok(500i128));
-    assert_eq!(setup.liquid_contract.read_lender(&lender2), Ok(500i128));
-    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
-    assert_eq!(
-        setup.liquid_contract.read_loan_amount(&borrower, loan_id),
-        2i128
-    );
-
+    assert_eq!(setup.liquid_contract.client().treasury(), treasury);
     assert_eq!(
-        contract_events,
+        vec![&setup.env, contract_events.last().unwrap()],
         vec![
             &setup.env,
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "initialize").as_val(),
+                    *Symbol::new(&setup.env, "set_treasury").as_val(),
                     setup.admin.into_val(&setup.env),
-                    setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
-            ),
+                treasury.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_claim_protocol_fees() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
+    let treasury = Address::generate(&setup.env);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_treasury(&setup.admin, &treasury);
+    setup.liquid_contract.execute_change(id);
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_protocol_fee(&setup.admin, &5000u32);
+    setup.liquid_contract.execute_change(id);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &10000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &10000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &10000i128, &LOAN_DURATION, &None, &None);
+
+    set_timestamp_for_20_days(&setup.env);
+    setup.token_admin.mint(&borrower, &20i128);
+    setup
+        .liquid_contract
+        .client()
+        .repay_in_full(&borrower, &loan_id);
+
+    assert_eq!(setup.liquid_contract.client().treasury_balance(), 10i128);
+    assert_eq!(setup.liquid_contract.read_lender(&lender), Ok(10010i128));
+    assert_eq!(setup.token.balance(&setup.admin), 0i128);
+
+    let amount = setup
+        .liquid_contract
+        .client()
+        .claim_protocol_fees(&setup.admin);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(amount, 10i128);
+    assert_eq!(setup.token.balance(&treasury), 10i128);
+    assert_eq!(setup.liquid_contract.client().treasury_balance(), 0i128);
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    *Symbol::new(&setup.env, "claim_protocol_fees").as_val(),
                     setup.admin.into_val(&setup.env),
-                    lender1.into_val(&setup.env),
+                    treasury.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
-            ),
+                10i128.into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_add_borrower() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &setup.admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "add_borrower",
+                args: (setup.admin.clone(), borrower.clone()).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .add_borrower(&setup.admin, &borrower);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert!(setup.liquid_contract.has_borrower(&borrower));
+    assert_eq!(
+        contract_events,
+        vec![
+            &setup.env,
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "add_lender").as_val(),
+                    *Symbol::new(&setup.env, "initialize").as_val(),
                     setup.admin.into_val(&setup.env),
-                    lender2.into_val(&setup.env),
+                    setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "deposit").as_val(),
-                    lender1.into_val(&setup.env),
+                    *Symbol::new(&setup.env, "add_borrower").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    borrower.into_val(&setup.env),
                 ],
-                500i128.into_val(&setup.env)
-            ),
+                ().into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_add_borrower_with_fake_admin() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+    let fake_admin = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &fake_admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "add_borrower",
+                args: (fake_admin.clone(), borrower.clone()).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .add_borrower(&fake_admin, &borrower);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_add_registered_borrower() {
+    let setup = Setup::new();
+
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+}
+
+#[test]
+fn test_set_borrower_status() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    assert!(setup.liquid_contract.has_borrower(&borrower));
+
+    setup
+        .liquid_contract
+        .client()
+        .set_borrower_status(&setup.admin, &borrower, &false);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup.liquid_contract.read_borrower_status(&borrower),
+        Ok(false)
+    );
+    assert_eq!(
+        contract_events,
+        vec![
+            &setup.env,
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "deposit").as_val(),
-                    lender2.into_val(&setup.env),
+                    *Symbol::new(&setup.env, "initialize").as_val(),
+                    setup.admin.into_val(&setup.env),
+                    setup.token.address.into_val(&setup.env),
                 ],
-                500i128.into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -1053,48 +6598,146 @@ fn test_repay_loan_without_repayment_total_amount() {
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "loan").as_val(),
+                    *Symbol::new(&setup.env, "set_borrower_status").as_val(),
+                    setup.admin.into_val(&setup.env),
                     borrower.into_val(&setup.env),
-                    loan_id.into_val(&setup.env),
                 ],
-                1000i128.into_val(&setup.env)
-            ),
+                (false).into_val(&setup.env)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_set_borrower_status_with_fake_admin() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+    let fake_admin = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .add_borrower(&setup.admin, &borrower);
+
+    assert!(setup.liquid_contract.has_borrower(&borrower));
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_auths(&[MockAuth {
+            address: &fake_admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "set_borrower_status",
+                args: (fake_admin.clone(), borrower.clone(), false).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .set_borrower_status(&fake_admin, &borrower, &false);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_set_borrower_status_without_borrower() {
+    let setup = Setup::new();
+    let borrower = Address::generate(&setup.env);
+
+    setup
+        .liquid_contract
+        .client()
+        .mock_all_auths()
+        .set_borrower_status(&setup.admin, &borrower, &false);
+}
+
+#[test]
+fn test_update_borrower_terms() {
+    let setup = Setup::new();
+    setup.env.mock_all_auths();
+    let borrower = Address::generate(&setup.env);
+    let terms = BorrowerTerms {
+        interest_rate: Some(5),
+        max_principal: Some(500i128),
+        max_tenor: Some(30 * 86400),
+        credit_limit: None,
+    };
+
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+
+    let id = setup
+        .liquid_contract
+        .client()
+        .update_borrower_terms(&setup.admin, &borrower, &terms);
+    setup.liquid_contract.execute_change(id);
+
+    let contract_events = setup.liquid_contract.get_contract_events();
+
+    assert_eq!(
+        setup.liquid_contract.read_borrower(&borrower),
+        Ok(Borrower {
+            active: true,
+            terms: terms.clone(),
+        })
+    );
+    assert_eq!(
+        vec![&setup.env, contract_events.last().unwrap()],
+        vec![
+            &setup.env,
             (
                 setup.liquid_contract_id.clone(),
                 vec![
                     &setup.env,
-                    *Symbol::new(&setup.env, "repay_loan").as_val(),
+                    *Symbol::new(&setup.env, "update_borrower_terms").as_val(),
+                    setup.admin.into_val(&setup.env),
                     borrower.into_val(&setup.env),
-                    loan_id.into_val(&setup.env),
                 ],
-                1000i128.into_val(&setup.env)
+                terms.into_val(&setup.env)
             )
         ]
     );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_repay_loan_negative_amount() {
+#[should_panic(expected = "Error(Contract, #54)")]
+fn test_update_borrower_terms_with_fake_admin() {
     let setup = Setup::new();
     let borrower = Address::generate(&setup.env);
+    let fake_admin = Address::generate(&setup.env);
+    let terms = BorrowerTerms {
+        interest_rate: Some(5),
+        max_principal: None,
+        max_tenor: None,
+        credit_limit: None,
+    };
 
     setup
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_borrower(&borrower);
+        .add_borrower(&setup.admin, &borrower);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .repay_loan(&borrower, &1u64, &-10i128);
+        .mock_auths(&[MockAuth {
+            address: &fake_admin,
+            invoke: &MockAuthInvoke {
+                contract: &setup.liquid_contract_id,
+                fn_name: "update_borrower_terms",
+                args: (fake_admin.clone(), borrower.clone(), terms.clone()).into_val(&setup.env),
+                sub_invokes: &[],
+            },
+        }])
+        .update_borrower_terms(&fake_admin, &borrower, &terms);
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #6)")]
-fn test_repay_loan_without_borrower() {
+fn test_update_borrower_terms_without_borrower() {
     let setup = Setup::new();
     let borrower = Address::generate(&setup.env);
 
@@ -1102,288 +6745,337 @@ fn test_repay_loan_without_borrower() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .repay_loan(&borrower, &1u64, &10i128);
+        .update_borrower_terms(
+            &setup.admin,
+            &borrower,
+            &BorrowerTerms {
+                interest_rate: Some(5),
+                max_principal: None,
+                max_tenor: None,
+                credit_limit: None,
+            },
+        );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #8)")]
-fn test_repay_loan_without_active_loan() {
+fn test_loan_with_borrower_interest_rate() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
-
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .repay_loan(&borrower, &1u64, &10i128);
-}
-
-#[test]
-fn test_repay_loan_amount() {
-    let setup = Setup::new();
-    setup.env.mock_all_auths();
-    let borrower = Address::generate(&setup.env);
-    let lender = Address::generate(&setup.env);
-
-    setup.liquid_contract.client().add_lender(&lender);
-
-    setup.token_admin.mock_all_auths().mint(&lender, &1000i128);
-    setup
-        .token_admin
-        .mock_all_auths()
-        .mint(&setup.liquid_contract_id, &1000i128);
-
+        .deposit(&lender, &1000i128, &Tranche::Senior);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .deposit(&lender, &1000i128);
-
-    assert_eq!(setup.liquid_contract.read_contract_balance(), 1000i128);
-
-    setup.liquid_contract.client().add_borrower(&borrower);
+        .add_borrower(&setup.admin, &borrower);
+    let id = setup.liquid_contract.client().update_borrower_terms(
+        &setup.admin,
+        &borrower,
+        &BorrowerTerms {
+            interest_rate: Some(5),
+            max_principal: None,
+            max_tenor: None,
+            credit_limit: None,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
 
-    let loan_id = setup
-        .liquid_contract
-        .client()
-        .mock_all_auths()
-        .loan(&borrower, &1000i128);
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
 
-    assert!(setup.liquid_contract.has_loan(&borrower, loan_id));
     set_timestamp_for_20_days(&setup.env);
 
     let loan_amount = setup
         .liquid_contract
         .client()
-        .mock_all_auths()
         .repay_loan_amount(&borrower, &loan_id);
 
-    assert_eq!(loan_amount, 1002i128);
+    assert_eq!(loan_amount, 1010i128);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #6)")]
-fn test_repay_loan_amount_without_borrower() {
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_loan_exceeds_borrower_max_principal() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .repay_loan_amount(&borrower, &1u64);
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    let id = setup.liquid_contract.client().update_borrower_terms(
+        &setup.admin,
+        &borrower,
+        &BorrowerTerms {
+            interest_rate: None,
+            max_principal: Some(500i128),
+            max_tenor: None,
+            credit_limit: None,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &600i128, &LOAN_DURATION, &None, &None);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #8)")]
-fn test_repay_loan_amount_without_active_loan() {
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_loan_exceeds_borrower_max_tenor() {
     let setup = Setup::new();
     setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
 
-    setup.liquid_contract.client().add_borrower(&borrower);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &1000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &1000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    let id = setup.liquid_contract.client().update_borrower_terms(
+        &setup.admin,
+        &borrower,
+        &BorrowerTerms {
+            interest_rate: None,
+            max_principal: None,
+            max_tenor: Some(10 * 86400),
+            credit_limit: None,
+        },
+    );
+    setup.liquid_contract.execute_change(id);
 
     setup
         .liquid_contract
         .client()
-        .repay_loan_amount(&borrower, &1u64);
+        .loan(&borrower, &500i128, &(20 * 86400), &None, &None);
 }
 
 #[test]
-fn test_add_borrower() {
+fn test_available_credit() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_auths(&[MockAuth {
-            address: &setup.admin,
-            invoke: &MockAuthInvoke {
-                contract: &setup.liquid_contract_id,
-                fn_name: "add_borrower",
-                args: (borrower.clone(),).into_val(&setup.env),
-                sub_invokes: &[],
-            },
-        }])
-        .add_borrower(&borrower);
-
-    let contract_events = setup.liquid_contract.get_contract_events();
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &2000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &2000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
 
-    assert!(setup.liquid_contract.has_borrower(&borrower));
     assert_eq!(
-        contract_events,
-        vec![
-            &setup.env,
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "initialize").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    setup.token.address.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_borrower").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    borrower.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            )
-        ]
+        setup.liquid_contract.client().available_credit(&borrower),
+        None
     );
-}
 
-#[test]
-#[should_panic = "Unauthorized function call for address"]
-fn test_add_borrower_with_fake_admin() {
-    let setup = Setup::new();
-    let borrower = Address::generate(&setup.env);
-    let fake_admin = Address::generate(&setup.env);
+    let id = setup.liquid_contract.client().update_borrower_terms(
+        &setup.admin,
+        &borrower,
+        &BorrowerTerms {
+            interest_rate: None,
+            max_principal: None,
+            max_tenor: None,
+            credit_limit: Some(1000i128),
+        },
+    );
+    setup.liquid_contract.execute_change(id);
 
     setup
         .liquid_contract
         .client()
-        .mock_auths(&[MockAuth {
-            address: &fake_admin,
-            invoke: &MockAuthInvoke {
-                contract: &setup.liquid_contract_id,
-                fn_name: "add_borrower",
-                args: (borrower.clone(),).into_val(&setup.env),
-                sub_invokes: &[],
-            },
-        }])
-        .add_borrower(&borrower);
+        .loan(&borrower, &600i128, &LOAN_DURATION, &None, &None);
+
+    assert_eq!(
+        setup.liquid_contract.client().available_credit(&borrower),
+        Some(400i128)
+    );
+
+    set_timestamp_for_20_days(&setup.env);
+
+    assert_eq!(
+        setup.liquid_contract.client().available_credit(&borrower),
+        Some(399i128)
+    );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
-fn test_add_registered_borrower() {
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_loan_exceeds_credit_limit_across_open_loans() {
     let setup = Setup::new();
-
+    setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &2000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &2000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    let id = setup.liquid_contract.client().update_borrower_terms(
+        &setup.admin,
+        &borrower,
+        &BorrowerTerms {
+            interest_rate: None,
+            max_principal: None,
+            max_tenor: None,
+            credit_limit: Some(1000i128),
+        },
+    );
+    setup.liquid_contract.execute_change(id);
 
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
+        .loan(&borrower, &600i128, &LOAN_DURATION, &None, &None);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &500i128, &LOAN_DURATION, &None, &None);
 }
 
 #[test]
-fn test_set_borrower_status() {
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_loan_after_default_exceeds_credit_limit() {
     let setup = Setup::new();
     setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
-
-    setup.liquid_contract.client().add_borrower(&borrower);
-
-    assert!(setup.liquid_contract.has_borrower(&borrower));
+    let lender = Address::generate(&setup.env);
 
     setup
         .liquid_contract
         .client()
-        .set_borrower_status(&borrower, &false);
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &2000i128);
+    setup
+        .liquid_contract
+        .client()
+        .deposit(&lender, &2000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
+    let id = setup.liquid_contract.client().update_borrower_terms(
+        &setup.admin,
+        &borrower,
+        &BorrowerTerms {
+            interest_rate: None,
+            max_principal: None,
+            max_tenor: None,
+            credit_limit: Some(1000i128),
+        },
+    );
+    setup.liquid_contract.execute_change(id);
 
-    let contract_events = setup.liquid_contract.get_contract_events();
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
+    setup
+        .liquid_contract
+        .client()
+        .mark_default(&setup.admin, &borrower, &loan_id);
 
-    assert_eq!(setup.liquid_contract.read_borrower(&borrower), Ok(false));
     assert_eq!(
-        contract_events,
-        vec![
-            &setup.env,
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "initialize").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    setup.token.address.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "add_borrower").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    borrower.into_val(&setup.env),
-                ],
-                ().into_val(&setup.env)
-            ),
-            (
-                setup.liquid_contract_id.clone(),
-                vec![
-                    &setup.env,
-                    *Symbol::new(&setup.env, "set_borrower_status").as_val(),
-                    setup.admin.into_val(&setup.env),
-                    borrower.into_val(&setup.env),
-                ],
-                (false).into_val(&setup.env)
-            )
-        ]
+        setup.liquid_contract.client().available_credit(&borrower),
+        Some(0i128)
     );
+
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &1i128, &LOAN_DURATION, &None, &None);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized function call for address")]
-fn test_set_borrower_status_with_fake_admin() {
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_loan_after_default_exceeds_max_open_loans() {
     let setup = Setup::new();
+    setup.env.mock_all_auths();
     let borrower = Address::generate(&setup.env);
-    let fake_admin = Address::generate(&setup.env);
+    let lender = Address::generate(&setup.env);
 
+    let id = setup
+        .liquid_contract
+        .client()
+        .set_max_open_loans_per_borrower(&setup.admin, &Some(1u32));
+    setup.liquid_contract.execute_change(id);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .add_borrower(&borrower);
-
-    assert!(setup.liquid_contract.has_borrower(&borrower));
-
+        .add_lender(&setup.admin, &lender);
+    setup.token_admin.mint(&lender, &2000i128);
     setup
         .liquid_contract
         .client()
-        .mock_auths(&[MockAuth {
-            address: &fake_admin,
-            invoke: &MockAuthInvoke {
-                contract: &setup.liquid_contract_id,
-                fn_name: "set_borrower_status",
-                args: (borrower.clone(), false).into_val(&setup.env),
-                sub_invokes: &[],
-            },
-        }])
-        .set_borrower_status(&borrower, &false);
-}
-
-#[test]
-#[should_panic(expected = "Error(Contract, #6)")]
-fn test_set_borrower_status_without_borrower() {
-    let setup = Setup::new();
-    let borrower = Address::generate(&setup.env);
+        .deposit(&lender, &2000i128, &Tranche::Senior);
+    setup
+        .liquid_contract
+        .client()
+        .add_borrower(&setup.admin, &borrower);
 
+    let loan_id =
+        setup
+            .liquid_contract
+            .client()
+            .loan(&borrower, &1000i128, &LOAN_DURATION, &None, &None);
     setup
         .liquid_contract
         .client()
-        .mock_all_auths()
-        .set_borrower_status(&borrower, &false);
+        .mark_default(&setup.admin, &borrower, &loan_id);
+    setup
+        .liquid_contract
+        .client()
+        .loan(&borrower, &500i128, &LOAN_DURATION, &None, &None);
 }
 
 #[test]
@@ -1395,7 +7087,7 @@ fn test_remove_borrower() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_borrower(&borrower);
+        .add_borrower(&setup.admin, &borrower);
 
     assert!(setup.liquid_contract.has_borrower(&borrower));
 
@@ -1407,11 +7099,11 @@ fn test_remove_borrower() {
             invoke: &MockAuthInvoke {
                 contract: &setup.liquid_contract_id,
                 fn_name: "remove_borrower",
-                args: (borrower.clone(),).into_val(&setup.env),
+                args: (setup.admin.clone(), borrower.clone()).into_val(&setup.env),
                 sub_invokes: &[],
             },
         }])
-        .remove_borrower(&borrower);
+        .remove_borrower(&setup.admin, &borrower);
 
     let contract_events = setup.liquid_contract.get_contract_events();
 
@@ -1428,7 +7120,7 @@ fn test_remove_borrower() {
                     setup.admin.into_val(&setup.env),
                     setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -1455,7 +7147,7 @@ fn test_remove_borrower() {
 }
 
 #[test]
-#[should_panic = "Unauthorized function call for address"]
+#[should_panic(expected = "Error(Contract, #54)")]
 fn test_remove_borrower_with_fake_admin() {
     let setup = Setup::new();
     let borrower = Address::generate(&setup.env);
@@ -1465,7 +7157,7 @@ fn test_remove_borrower_with_fake_admin() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_borrower(&borrower);
+        .add_borrower(&setup.admin, &borrower);
 
     assert!(setup.liquid_contract.has_borrower(&borrower));
 
@@ -1477,11 +7169,11 @@ fn test_remove_borrower_with_fake_admin() {
             invoke: &MockAuthInvoke {
                 contract: &setup.liquid_contract_id,
                 fn_name: "remove_borrower",
-                args: (borrower.clone(),).into_val(&setup.env),
+                args: (fake_admin.clone(), borrower.clone()).into_val(&setup.env),
                 sub_invokes: &[],
             },
         }])
-        .remove_borrower(&borrower);
+        .remove_borrower(&fake_admin, &borrower);
 }
 
 #[test]
@@ -1494,7 +7186,7 @@ fn test_remove_without_borrower() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .remove_borrower(&borrower);
+        .remove_borrower(&setup.admin, &borrower);
 }
 
 #[test]
@@ -1510,11 +7202,11 @@ fn test_add_lender() {
             invoke: &MockAuthInvoke {
                 contract: &setup.liquid_contract_id,
                 fn_name: "add_lender",
-                args: (lender.clone(),).into_val(&setup.env),
+                args: (setup.admin.clone(), lender.clone()).into_val(&setup.env),
                 sub_invokes: &[],
             },
         }])
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
 
     let contract_events = setup.liquid_contract.get_contract_events();
 
@@ -1531,7 +7223,7 @@ fn test_add_lender() {
                     setup.admin.into_val(&setup.env),
                     setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -1548,7 +7240,7 @@ fn test_add_lender() {
 }
 
 #[test]
-#[should_panic = "Unauthorized function call for address"]
+#[should_panic(expected = "Error(Contract, #54)")]
 fn test_add_lender_with_fake_admin() {
     let setup = Setup::new();
     let lender = Address::generate(&setup.env);
@@ -1562,11 +7254,11 @@ fn test_add_lender_with_fake_admin() {
             invoke: &MockAuthInvoke {
                 contract: &setup.liquid_contract_id,
                 fn_name: "add_lender",
-                args: (lender.clone(),).into_val(&setup.env),
+                args: (fake_admin.clone(), lender.clone()).into_val(&setup.env),
                 sub_invokes: &[],
             },
         }])
-        .add_lender(&lender);
+        .add_lender(&fake_admin, &lender);
 }
 
 #[test]
@@ -1579,7 +7271,7 @@ fn test_add_registered_lender() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
 
     assert!(setup.liquid_contract.has_lender(&lender));
 
@@ -1587,7 +7279,7 @@ fn test_add_registered_lender() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
 }
 
 #[test]
@@ -1599,7 +7291,7 @@ fn test_remove_lender() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
 
     assert!(setup.liquid_contract.has_lender(&lender));
 
@@ -1611,11 +7303,11 @@ fn test_remove_lender() {
             invoke: &MockAuthInvoke {
                 contract: &setup.liquid_contract_id,
                 fn_name: "remove_lender",
-                args: (lender.clone(),).into_val(&setup.env),
+                args: (setup.admin.clone(), lender.clone()).into_val(&setup.env),
                 sub_invokes: &[],
             },
         }])
-        .remove_lender(&lender);
+        .remove_lender(&setup.admin, &lender);
 
     let contract_events = setup.liquid_contract.get_contract_events();
 
@@ -1632,7 +7324,7 @@ fn test_remove_lender() {
                     setup.admin.into_val(&setup.env),
                     setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -1659,7 +7351,7 @@ fn test_remove_lender() {
 }
 
 #[test]
-#[should_panic = "Unauthorized function call for address"]
+#[should_panic(expected = "Error(Contract, #54)")]
 fn test_remove_lender_with_fake_admin() {
     let setup = Setup::new();
     let lender = Address::generate(&setup.env);
@@ -1669,7 +7361,7 @@ fn test_remove_lender_with_fake_admin() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
 
     assert!(setup.liquid_contract.has_lender(&lender));
 
@@ -1681,11 +7373,11 @@ fn test_remove_lender_with_fake_admin() {
             invoke: &MockAuthInvoke {
                 contract: &setup.liquid_contract_id,
                 fn_name: "remove_lender",
-                args: (lender.clone(),).into_val(&setup.env),
+                args: (fake_admin.clone(), lender.clone()).into_val(&setup.env),
                 sub_invokes: &[],
             },
         }])
-        .remove_lender(&lender);
+        .remove_lender(&fake_admin, &lender);
 }
 
 #[test]
@@ -1698,7 +7390,7 @@ fn test_remove_without_lender() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .remove_lender(&lender);
+        .remove_lender(&setup.admin, &lender);
 }
 
 #[test]
@@ -1707,13 +7399,16 @@ fn test_set_lender_status() {
     setup.env.mock_all_auths();
     let lender = Address::generate(&setup.env);
 
-    setup.liquid_contract.client().add_lender(&lender);
+    setup
+        .liquid_contract
+        .client()
+        .add_lender(&setup.admin, &lender);
     assert!(setup.liquid_contract.has_lender(&lender));
 
     setup
         .liquid_contract
         .client()
-        .set_lender_status(&lender, &false);
+        .set_lender_status(&setup.admin, &lender, &false);
 
     assert_eq!(setup.liquid_contract.read_lender_status(&lender), Ok(false));
     let contract_events = setup.liquid_contract.get_contract_events();
@@ -1730,7 +7425,7 @@ fn test_set_lender_status() {
                     setup.admin.into_val(&setup.env),
                     setup.token.address.into_val(&setup.env),
                 ],
-                ().into_val(&setup.env)
+                INTEREST_RATE.into_val(&setup.env)
             ),
             (
                 setup.liquid_contract_id.clone(),
@@ -1766,11 +7461,11 @@ fn test_set_lender_status_not_registered() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .set_lender_status(&lender, &false);
+        .set_lender_status(&setup.admin, &lender, &false);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized function call for address")]
+#[should_panic(expected = "Error(Contract, #54)")]
 fn test_set_lender_status_with_fake_admin() {
     let setup = Setup::new();
     let lender = Address::generate(&setup.env);
@@ -1780,7 +7475,7 @@ fn test_set_lender_status_with_fake_admin() {
         .liquid_contract
         .client()
         .mock_all_auths()
-        .add_lender(&lender);
+        .add_lender(&setup.admin, &lender);
 
     assert!(setup.liquid_contract.has_lender(&lender));
 
@@ -1792,9 +7487,9 @@ fn test_set_lender_status_with_fake_admin() {
             invoke: &MockAuthInvoke {
                 contract: &setup.liquid_contract_id,
                 fn_name: "set_lender_status",
-                args: (lender.clone(), false).into_val(&setup.env),
+                args: (fake_admin.clone(), lender.clone(), false).into_val(&setup.env),
                 sub_invokes: &[],
             },
         }])
-        .set_lender_status(&lender, &false);
+        .set_lender_status(&fake_admin, &lender, &false);
 }
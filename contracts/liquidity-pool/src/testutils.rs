@@ -1,15 +1,28 @@
 #![cfg(test)]
 
 use crate::errors::LPError;
+use crate::shares::convert_to_assets;
 use crate::storage::{
-    has_borrower, has_lender, read_admin, read_borrower, read_contract_balance, read_contributions,
-    read_lender, read_loans, read_token,
+    has_borrower, has_lender, read_admin, read_borrower, read_contract_balance,
+    read_legacy_contributions, read_lender, read_loans, read_shares, read_token,
+    write_contract_balance,
 };
+use crate::types::{Borrower, CollateralConfig, DataKey, LegacyLender, LegacyLoan, Tranche};
 use crate::LiquidityPoolContractClient;
+use mock_oracle::{Asset, MockOracle, MockOracleClient};
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
     token::{self, StellarAssetClient},
-    vec, Address, Env, Val, Vec,
+    vec, Address, Env, Symbol, Val, Vec,
+};
+
+pub const INTEREST_RATE: u32 = 1;
+pub const LOAN_DURATION: u64 = 30 * 86400;
+pub const ORACLE_PRICE: i128 = 10_000_000;
+pub const COLLATERAL_CONFIG: CollateralConfig = CollateralConfig {
+    ltv: 5000,
+    liquidation_threshold: 8000,
+    liquidation_bonus: 500,
 };
 
 pub fn set_timestamp_for_20_days(env: &Env) {
@@ -29,7 +42,7 @@ pub fn create_test_contract(
     let contract_id = register_test_contract(env);
     let contract = LiquidityPoolContract::new(env, contract_id.clone());
 
-    contract.client().initialize(admin, token);
+    contract.client().initialize(admin, token, &INTEREST_RATE);
 
     (contract_id, contract)
 }
@@ -42,13 +55,24 @@ pub fn create_token_contract<'a>(
     env: &Env,
     admin: &Address,
 ) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
-    let contract_address = env.register_stellar_asset_contract(admin.clone());
+    let contract_address = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
     (
         token::Client::new(env, &contract_address),
         token::StellarAssetClient::new(env, &contract_address),
     )
 }
 
+pub fn create_oracle_contract<'a>(env: &Env) -> MockOracleClient<'a> {
+    let contract_address = env.register_contract(None, MockOracle {});
+    MockOracleClient::new(env, &contract_address)
+}
+
+pub fn set_oracle_price(oracle: &MockOracleClient, token: &Address, price: i128) {
+    oracle.set_price(&Asset::Stellar(token.clone()), &price);
+}
+
 pub struct Setup<'a> {
     pub env: Env,
     pub admin: Address,
@@ -85,9 +109,26 @@ impl Setup<'_> {
     }
 }
 
+impl<'a> Setup<'a> {
+    pub fn create_oracle(&self, collateral_token: &Address) -> MockOracleClient<'a> {
+        let oracle = create_oracle_contract(&self.env);
+
+        let id = self
+            .liquid_contract
+            .client()
+            .set_oracle(&self.admin, &oracle.address);
+        self.liquid_contract.execute_change(id);
+
+        set_oracle_price(&oracle, &self.token.address, ORACLE_PRICE);
+        set_oracle_price(&oracle, collateral_token, ORACLE_PRICE);
+
+        oracle
+    }
+}
+
 impl LiquidityPoolContract {
     #[must_use]
-    pub fn client(&self) -> LiquidityPoolContractClient {
+    pub fn client(&self) -> LiquidityPoolContractClient<'_> {
         LiquidityPoolContractClient::new(&self.env, &self.contract_id)
     }
 
@@ -99,6 +140,23 @@ impl LiquidityPoolContract {
         }
     }
 
+    /// Moves the ledger time forward to the execution time of a queued change
+    /// if needed and executes it.
+    pub fn execute_change(&self, id: u64) {
+        let queued = self
+            .client()
+            .queued_changes()
+            .iter()
+            .find(|queued| queued.id == id)
+            .unwrap();
+
+        if self.env.ledger().timestamp() < queued.execution_time {
+            self.env.ledger().set_timestamp(queued.execution_time);
+        }
+
+        self.client().execute_change(&id);
+    }
+
     pub fn get_contract_events(&self) -> Vec<(Address, Vec<Val>, Val)> {
         let mut contract_events = vec![&self.env];
 
@@ -143,31 +201,51 @@ impl LiquidityPoolContract {
         })
     }
 
-    pub fn read_borrower(&self, borrower: &Address) -> Result<bool, LPError> {
+    pub fn read_borrower(&self, borrower: &Address) -> Result<Borrower, LPError> {
         self.env.as_contract(&self.contract_id, || {
             let borrower = read_borrower(&self.env, borrower)?;
             Ok(borrower)
         })
     }
 
+    pub fn read_borrower_status(&self, borrower: &Address) -> Result<bool, LPError> {
+        self.env.as_contract(&self.contract_id, || {
+            let borrower = read_borrower(&self.env, borrower)?;
+            Ok(borrower.active)
+        })
+    }
+
     pub fn read_contract_balance(&self) -> i128 {
         self.env
             .as_contract(&self.contract_id, || read_contract_balance(&self.env))
     }
 
-    pub fn read_loan_amount(&self, borrower: &Address, loan_id: u64) -> i128 {
+    pub fn read_loan_principal(&self, borrower: &Address, loan_id: u64) -> i128 {
+        self.env.as_contract(&self.contract_id, || {
+            let loans = read_loans(&self.env, borrower);
+            let loan = loans.iter().find(|loan| loan.id == loan_id).unwrap();
+
+            loan.principal
+        })
+    }
+
+    pub fn read_loan_interest_rate(&self, borrower: &Address, loan_id: u64) -> u32 {
         self.env.as_contract(&self.contract_id, || {
             let loans = read_loans(&self.env, borrower);
             let loan = loans.iter().find(|loan| loan.id == loan_id).unwrap();
 
-            loan.amount
+            loan.interest_rate
         })
     }
 
     pub fn read_lender(&self, lender: &Address) -> Result<i128, LPError> {
         self.env.as_contract(&self.contract_id, || {
-            let lender = read_lender(&self.env, lender)?;
-            Ok(lender.balance)
+            read_lender(&self.env, lender)?;
+            let senior = read_shares(&self.env, Tranche::Senior, lender);
+            let junior = read_shares(&self.env, Tranche::Junior, lender);
+
+            Ok(convert_to_assets(&self.env, Tranche::Senior, senior)
+                + convert_to_assets(&self.env, Tranche::Junior, junior))
         })
     }
 
@@ -178,10 +256,66 @@ impl LiquidityPoolContract {
         })
     }
 
-    pub fn is_lender_in_contributions(&self, lender: &Address) -> bool {
+    pub fn has_shares(&self, lender: &Address) -> bool {
+        self.env.as_contract(&self.contract_id, || {
+            read_shares(&self.env, Tranche::Senior, lender)
+                + read_shares(&self.env, Tranche::Junior, lender)
+                > 0
+        })
+    }
+
+    pub fn write_legacy_borrower(&self, borrower: &Address, active: bool) {
+        self.env.as_contract(&self.contract_id, || {
+            self.env
+                .storage()
+                .persistent()
+                .set(&DataKey::Borrower(borrower.clone()), &active)
+        })
+    }
+
+    pub fn write_legacy_loans(&self, borrower: &Address, loans: &Vec<LegacyLoan>) {
+        self.env.as_contract(&self.contract_id, || {
+            self.env
+                .storage()
+                .persistent()
+                .set(&DataKey::Loan(borrower.clone()), loans)
+        })
+    }
+
+    pub fn write_legacy_lender(&self, lender: &Address, balance: i128) {
+        self.env.as_contract(&self.contract_id, || {
+            self.env.storage().persistent().set(
+                &DataKey::Lender(lender.clone()),
+                &LegacyLender {
+                    active: true,
+                    balance,
+                },
+            );
+            let mut contributions = read_legacy_contributions(&self.env);
+            contributions.push_back(lender.clone());
+            self.env
+                .storage()
+                .persistent()
+                .set(&(Symbol::new(&self.env, "Contribution"),), &contributions);
+            write_contract_balance(&self.env, &(read_contract_balance(&self.env) + balance));
+        })
+    }
+
+    pub fn has_legacy_contributions(&self) -> bool {
+        self.env.as_contract(&self.contract_id, || {
+            self.env
+                .storage()
+                .persistent()
+                .has(&(Symbol::new(&self.env, "Contribution"),))
+        })
+    }
+
+    pub fn remove_schema_version(&self) {
         self.env.as_contract(&self.contract_id, || {
-            let contributions = read_contributions(&self.env);
-            contributions.iter().any(|address| address == *lender)
+            self.env
+                .storage()
+                .persistent()
+                .remove(&DataKey::SchemaVersion)
         })
     }
 }
@@ -0,0 +1,76 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::errors::LPError;
+use crate::storage::{
+    read_change_queue_head, read_min_delay, read_next_change_id, read_queued_change,
+    remove_queued_change, write_change_queue_head, write_next_change_id, write_queued_change,
+};
+use crate::types::{ParameterChange, QueuedChange, Role};
+
+/// Shortest delay a change can be queued for, whatever the stored delay.
+pub const MIN_DELAY: u64 = 86_400;
+
+/// Role allowed to queue and cancel a change, or `None` when only the admin is.
+pub fn required_role(change: &ParameterChange) -> Option<Role> {
+    match change {
+        ParameterChange::ProtocolFee(_) | ParameterChange::Treasury(_) => Some(Role::Treasurer),
+        ParameterChange::GrantRole(..)
+        | ParameterChange::MinDelay(_)
+        | ParameterChange::Upgrade(_)
+        | ParameterChange::ProposeAdmin(_) => None,
+        _ => Some(Role::RiskManager),
+    }
+}
+
+pub fn calculate_min_delay(env: &Env) -> u64 {
+    read_min_delay(env).max(MIN_DELAY)
+}
+
+pub fn find_change(env: &Env, id: u64) -> Result<QueuedChange, LPError> {
+    read_queued_change(env, id).ok_or(LPError::ChangeNotFound)
+}
+
+/// Changes still waiting in the queue, from the oldest one.
+pub fn list_changes(env: &Env) -> Vec<QueuedChange> {
+    let mut changes = Vec::new(env);
+
+    for id in read_change_queue_head(env)..read_next_change_id(env) {
+        if let Some(queued) = read_queued_change(env, id) {
+            changes.push_back(queued);
+        }
+    }
+
+    changes
+}
+
+pub fn enqueue_change(env: &Env, caller: &Address, change: ParameterChange) -> QueuedChange {
+    let id = read_next_change_id(env);
+    let queued = QueuedChange {
+        id,
+        caller: caller.clone(),
+        change,
+        execution_time: env.ledger().timestamp() + calculate_min_delay(env),
+    };
+
+    write_queued_change(env, &queued);
+    write_next_change_id(env, &(id + 1));
+
+    queued
+}
+
+pub fn dequeue_change(env: &Env, id: u64) -> Result<QueuedChange, LPError> {
+    let queued = find_change(env, id)?;
+
+    remove_queued_change(env, id);
+
+    let tail = read_next_change_id(env);
+    let mut head = read_change_queue_head(env);
+
+    while head < tail && read_queued_change(env, head).is_none() {
+        head += 1;
+    }
+
+    write_change_queue_head(env, &head);
+
+    Ok(queued)
+}
@@ -0,0 +1,92 @@
+use soroban_sdk::Env;
+
+use crate::errors::LPError;
+use crate::interest_rate::BASIS_POINTS;
+use crate::shares::{calculate_tranche_assets, distribute_income, distribute_loss};
+use crate::storage::{
+    read_junior_assets, read_junior_premium, read_max_senior_ratio, read_senior_loss,
+    read_total_shares, write_junior_assets, write_senior_loss,
+};
+use crate::types::Tranche;
+
+/// Credits the junior tranche with its part of the lenders' income, a pro rata
+/// share plus the junior premium taken from the senior part, and returns it.
+pub fn allocate_income(env: &Env, income: i128) -> i128 {
+    let junior_assets = read_junior_assets(env);
+
+    if income <= 0 || junior_assets <= 0 || read_total_shares(env, Tranche::Junior) == 0 {
+        return 0;
+    }
+
+    let senior_assets = calculate_tranche_assets(env, Tranche::Senior).max(0);
+    let junior_income = income * junior_assets / (senior_assets + junior_assets);
+    let premium = (income - junior_income) * read_junior_premium(env) as i128 / BASIS_POINTS;
+
+    write_junior_assets(env, &(junior_assets + junior_income + premium));
+    distribute_income(env, Tranche::Junior, junior_income + premium);
+
+    junior_income + premium
+}
+
+/// Writes off a loss against the junior tranche first and the senior tranche
+/// with the remainder.
+pub fn allocate_loss(env: &Env, amount: i128) {
+    let junior_assets = read_junior_assets(env);
+    let junior_loss = amount.min(junior_assets);
+    let senior_loss = amount - junior_loss;
+
+    write_junior_assets(env, &(junior_assets - junior_loss));
+    write_senior_loss(env, &(read_senior_loss(env) + senior_loss));
+
+    distribute_loss(env, Tranche::Junior, junior_loss);
+    distribute_loss(env, Tranche::Senior, senior_loss);
+}
+
+/// Restores the senior tranche losses first and credits the rest of a
+/// recovery to the junior tranche.
+pub fn allocate_recovery(env: &Env, amount: i128) {
+    let senior_loss = read_senior_loss(env);
+    let mut senior_recovery = amount.min(senior_loss);
+
+    if read_total_shares(env, Tranche::Junior) == 0 {
+        senior_recovery = amount;
+    }
+
+    let junior_recovery = amount - senior_recovery;
+
+    write_senior_loss(env, &(senior_loss - senior_recovery.min(senior_loss)));
+    write_junior_assets(env, &(read_junior_assets(env) + junior_recovery));
+
+    distribute_loss(env, Tranche::Senior, -senior_recovery);
+    distribute_loss(env, Tranche::Junior, -junior_recovery);
+}
+
+pub fn check_senior_ratio(env: &Env) -> Result<(), LPError> {
+    if let Some(ratio) = read_max_senior_ratio(env) {
+        let senior_assets = calculate_tranche_assets(env, Tranche::Senior);
+
+        if senior_assets * BASIS_POINTS > read_junior_assets(env) * ratio as i128 {
+            return Err(LPError::SeniorRatioExceeded);
+        }
+    }
+
+    Ok(())
+}
+
+/// Junior assets that can leave the pool without breaking the maximum senior
+/// ratio.
+pub fn calculate_junior_withdrawable(env: &Env) -> i128 {
+    let junior_assets = read_junior_assets(env).max(0);
+    let senior_assets = calculate_tranche_assets(env, Tranche::Senior);
+
+    match read_max_senior_ratio(env) {
+        Some(_) if senior_assets <= 0 => junior_assets,
+        Some(0) => 0,
+        Some(ratio) => {
+            let ratio = ratio as i128;
+            let required = (senior_assets * BASIS_POINTS + ratio - 1) / ratio;
+            (junior_assets - required).max(0)
+        }
+        None => junior_assets,
+    }
+}
@@ -1,19 +1,270 @@
-use soroban_sdk::{contracttype, Address, Map};
+use soroban_sdk::{contracttype, Address, BytesN, Map, Vec};
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum LoanStatus {
+    Active,
+    Overdue,
+    Defaulted,
+    Repaid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Collateral {
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CollateralConfig {
+    pub ltv: u32,
+    pub liquidation_threshold: u32,
+    pub liquidation_bonus: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RepaymentSchedule {
+    pub installments: u32,
+    pub period: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Installment {
+    pub due_time: u64,
+    pub amount: i128,
+    pub paid: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LoanSchedule {
+    pub installments: Vec<Installment>,
+    pub paid: i128,
+    pub due: i128,
+    pub overdue: i128,
+    pub accrued: i128,
+}
+
+/// Lender layout of schema version 1, holding the lender's balance that was not
+/// lent out instead of shares.
 #[derive(Clone)]
 #[contracttype]
-pub struct Loan {
+pub struct LegacyLender {
+    pub active: bool,
+    pub balance: i128,
+}
+
+/// Loan layout of schema version 1, before loans tracked their own principal,
+/// interest and due date.
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyLoan {
     pub id: u64,
     pub amount: i128,
     pub start_time: u64,
     pub contributions: Map<Address, i64>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Loan {
+    pub id: u64,
+    pub amount: i128,
+    pub principal: i128,
+    pub origination_fee: i128,
+    pub interest_paid: i128,
+    pub accrued_interest: i128,
+    pub accrued_fees: i128,
+    pub start_time: u64,
+    pub last_accrual_time: u64,
+    pub due_time: u64,
+    pub interest_rate: u32,
+    pub status: LoanStatus,
+    pub installments: Vec<Installment>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum OriginationFeeMode {
+    Deducted,
+    Financed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct OriginationFee {
+    pub rate: u32,
+    pub mode: OriginationFeeMode,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RepaymentSplit {
+    pub fees: i128,
+    pub interest: i128,
+    pub principal: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MaturityTerms {
+    pub max_tenor: Option<u64>,
+    pub grace_period: u64,
+    pub penalty_rate: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct InterestRateModel {
+    pub base_rate: u32,
+    pub slope1: u32,
+    pub kink: u32,
+    pub slope2: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BorrowerTerms {
+    pub interest_rate: Option<u32>,
+    pub max_principal: Option<i128>,
+    pub max_tenor: Option<u64>,
+    pub credit_limit: Option<i128>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Borrower {
+    pub active: bool,
+    pub terms: BorrowerTerms,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Lender {
     pub active: bool,
-    pub balance: i128,
+    pub deposited: i128,
+    pub withdrawn: i128,
+    pub interest_earned: i128,
+    pub losses: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LenderSummary {
+    pub active: bool,
+    pub deposited: i128,
+    pub withdrawn: i128,
+    pub interest_earned: i128,
+    pub losses: i128,
+    pub value: i128,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Operation {
+    Deposit,
+    Loan,
+    Withdraw,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PauseStatus {
+    pub deposit: bool,
+    pub loan: bool,
+    pub withdraw: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Role {
+    Operator,
+    RiskManager,
+    Pauser,
+    Treasurer,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Tranche {
+    Senior,
+    Junior,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DepositTerm {
+    Days30,
+    Days90,
+    Days180,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LockedPosition {
+    pub id: u64,
+    pub term: DepositTerm,
+    pub amount: i128,
+    pub shares: i128,
+    pub multiplier: u32,
+    pub start_time: u64,
+    pub maturity_time: u64,
+    pub boost_index: i128,
+    pub boost: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct WithdrawalRequest {
+    pub id: u64,
+    pub lender: Address,
+    pub tranche: Tranche,
+    pub shares: i128,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct Allowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Parameter change held by the timelock until its execution time.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ParameterChange {
+    InterestRate(u32),
+    InterestRateModel(InterestRateModel),
+    MaxOpenLoans(Option<u32>),
+    OriginationFee(OriginationFee),
+    TermMultiplier(DepositTerm, u32),
+    MaxSeniorRatio(u32),
+    JuniorPremium(u32),
+    ProtocolFee(u32),
+    Treasury(Address),
+    Oracle(Address),
+    MaxPriceAge(u64),
+    MaturityTerms(MaturityTerms),
+    CollateralToken(Address, CollateralConfig),
+    RemoveCollateralToken(Address),
+    BorrowerTerms(Address, BorrowerTerms),
+    GrantRole(Role, Address),
+    MinDelay(u64),
+    Upgrade(BytesN<32>),
+    ProposeAdmin(Address),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct QueuedChange {
+    pub id: u64,
+    pub caller: Address,
+    pub change: ParameterChange,
+    pub execution_time: u64,
 }
 
 #[derive(Clone)]
@@ -22,8 +273,49 @@ pub enum DataKey {
     TotalBalance,
     Token,
     Admin,
-    Contribution,
+    PendingAdmin,
+    Role(Role, Address),
+    PauseStatus,
+    SchemaVersion,
+    MinDelay,
+    NextChangeId,
+    ChangeQueueHead,
+    QueuedChange(u64),
+    InterestRateModel,
+    OutstandingPrincipal,
+    MaxOpenLoans,
+    MaturityTerms,
+    Oracle,
+    MaxPriceAge,
+    OriginationFee,
+    ProtocolFee,
+    Treasury,
+    TreasuryBalance,
+    JuniorAssets,
+    SeniorLoss,
+    MaxSeniorRatio,
+    JuniorPremium,
+    QueuedShares(Tranche),
+    WithdrawalQueueHead,
+    WithdrawalQueueTail,
+    BoostIndex,
+    BoostWeight,
+    BoostBalance,
+    TermMultiplier(DepositTerm),
     Borrower(Address),
     Lender(Address),
+    TotalShares(Tranche),
+    LossIndex(Tranche),
+    IncomeIndex(Tranche),
+    Shares(Tranche, Address),
+    Allowance(Address, Address),
     Loan(Address),
+    LossCheckpoint(Tranche, Address),
+    IncomeCheckpoint(Tranche, Address),
+    WithdrawalRequest(u64),
+    LenderWithdrawal(Tranche, Address),
+    LockedShares(Address),
+    Positions(Address),
+    CollateralToken(Address),
+    LoanCollateral(Address, u64),
 }
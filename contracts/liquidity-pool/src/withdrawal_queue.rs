@@ -0,0 +1,114 @@
+use soroban_sdk::{Address, Env};
+
+use crate::errors::LPError;
+use crate::shares::{convert_to_assets, transfer_shares};
+use crate::storage::{
+    read_contract_balance, read_lender_withdrawal, read_queued_shares, read_withdrawal_queue_head,
+    read_withdrawal_queue_tail, read_withdrawal_request, remove_withdrawal_request,
+    write_queued_shares, write_withdrawal_queue_tail, write_withdrawal_request,
+};
+use crate::tranches::calculate_junior_withdrawable;
+use crate::types::{Tranche, WithdrawalRequest};
+
+/// Value of the queued withdrawal requests that can be paid out, leaving out
+/// the junior requests blocked by the maximum senior ratio.
+pub fn calculate_queued_assets(env: &Env) -> i128 {
+    let junior_queued = convert_to_assets(
+        env,
+        Tranche::Junior,
+        read_queued_shares(env, Tranche::Junior),
+    );
+
+    convert_to_assets(
+        env,
+        Tranche::Senior,
+        read_queued_shares(env, Tranche::Senior),
+    ) + junior_queued.min(calculate_junior_withdrawable(env))
+}
+
+/// Contract balance that is not reserved for the queued withdrawal requests.
+pub fn calculate_available_balance(env: &Env) -> i128 {
+    (read_contract_balance(env) - calculate_queued_assets(env)).max(0)
+}
+
+pub fn find_withdrawal_request(
+    env: &Env,
+    tranche: Tranche,
+    lender: &Address,
+) -> Result<WithdrawalRequest, LPError> {
+    let id = read_lender_withdrawal(env, tranche, lender)?;
+
+    read_withdrawal_request(env, id).ok_or(LPError::WithdrawalRequestNotFound)
+}
+
+pub fn enqueue_withdrawal(
+    env: &Env,
+    tranche: Tranche,
+    lender: &Address,
+    shares: i128,
+) -> Result<u64, LPError> {
+    if read_lender_withdrawal(env, tranche, lender).is_ok() {
+        return Err(LPError::WithdrawalAlreadyRequested);
+    }
+
+    transfer_shares(
+        env,
+        tranche,
+        lender,
+        &env.current_contract_address(),
+        shares,
+    )?;
+
+    let id = read_withdrawal_queue_tail(env);
+
+    write_withdrawal_request(
+        env,
+        &WithdrawalRequest {
+            id,
+            lender: lender.clone(),
+            tranche,
+            shares,
+        },
+    );
+    write_withdrawal_queue_tail(env, &(id + 1));
+    write_queued_shares(env, tranche, &(read_queued_shares(env, tranche) + shares));
+
+    Ok(id)
+}
+
+pub fn dequeue_withdrawal(
+    env: &Env,
+    tranche: Tranche,
+    lender: &Address,
+) -> Result<WithdrawalRequest, LPError> {
+    let request = find_withdrawal_request(env, tranche, lender)?;
+
+    transfer_shares(
+        env,
+        tranche,
+        &env.current_contract_address(),
+        lender,
+        request.shares,
+    )?;
+
+    remove_withdrawal_request(env, &request);
+    write_queued_shares(
+        env,
+        tranche,
+        &(read_queued_shares(env, tranche) - request.shares),
+    );
+
+    Ok(request)
+}
+
+pub fn calculate_queue_position(env: &Env, id: u64) -> u32 {
+    let mut position = 0;
+
+    for queued_id in read_withdrawal_queue_head(env)..id {
+        if read_withdrawal_request(env, queued_id).is_some() {
+            position += 1;
+        }
+    }
+
+    position
+}